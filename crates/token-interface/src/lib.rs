@@ -0,0 +1,260 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_spl::token;
+use mpl_token_metadata::state::DataV2;
+use spl_token_2022::extension::{interest_bearing_mint, transfer_fee, ExtensionType, StateWithExtensions};
+
+pub use spl_associated_token_account::get_associated_token_address_with_program_id as get_associated_token_address;
+pub use spl_token_2022::state::{Account as TokenAccount, Mint};
+
+// NOTE anchor-spl 0.26 only knows the legacy Token program: Program<Token>
+// and Account<Mint/TokenAccount> require owner == spl_token::ID, and a
+// Token-2022 mint with extensions is longer than 82 bytes anyway.
+// This is a small stand-in for the later anchor_spl::token_interface:
+// - Mint/token accounts are UncheckedAccount and unpacked here, which
+//   works for both programs since Token-2022's base layout is the same.
+// - CPIs reuse anchor_spl::token's account structs, but build the
+//   instruction with spl_token_2022 against ctx.program's key, which
+//   accepts either program id.
+
+// NOTE The programs' instruction args (TokenExtensionsArgs, TransferFeeArgs,
+// TokenMetadataArgs) have the same shape as the types below, but must be
+// defined in each program: the 0.26 IDL parser only reads the program's
+// own crate. Each one has a From impl that names every field, so the two
+// can't drift without a compile error.
+
+// Token-2022 extensions for a new mint. Extensions can only be added when
+// the mint is created. The manager PDA is set as every extension's
+// authority (fee config, withheld fees, rate), same as it is for mint/freeze.
+#[derive(Clone, Debug, Default)]
+pub struct MintExtensions {
+    pub transfer_fee: Option<TransferFee>,
+    pub non_transferable: bool,
+    pub interest_rate: Option<i16>, // basis points
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TransferFee {
+    pub basis_points: u16,
+    pub maximum_fee: u64, // raw amount
+}
+
+impl MintExtensions {
+    pub fn extension_types(&self) -> Vec<ExtensionType> {
+        let mut extension_types = Vec::new();
+        if self.transfer_fee.is_some() {
+            extension_types.push(ExtensionType::TransferFeeConfig);
+        }
+        if self.non_transferable {
+            extension_types.push(ExtensionType::NonTransferable);
+        }
+        if self.interest_rate.is_some() {
+            extension_types.push(ExtensionType::InterestBearingConfig);
+        }
+        extension_types
+    }
+}
+
+// Metaplex metadata for a mint. Wallets/explorers read name, symbol and
+// uri from it instead of showing "Unknown token".
+// Max lengths are enforced by the Token Metadata Program (32/10/200).
+#[derive(Clone, Debug, Default)]
+pub struct TokenMetadata {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
+
+impl TokenMetadata {
+    // Metaplex metadata PDA seed prefix: ["metadata", metadata_program, mint]
+    pub const SEED_PREFIX: &'static str = "metadata";
+
+    pub fn to_data_v2(&self) -> DataV2 {
+        DataV2 {
+            name: self.name.clone(),
+            symbol: self.symbol.clone(),
+            uri: self.uri.clone(),
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: None,
+            uses: None,
+        }
+    }
+}
+
+pub fn is_token_program(key: &Pubkey) -> bool {
+    *key == token::ID || *key == spl_token_2022::ID
+}
+
+pub fn unpack_mint(info: &AccountInfo) -> Result<Mint> {
+    require!(is_token_program(info.owner), ErrorCode::AccountOwnedByWrongProgram);
+    let data = info.try_borrow_data()?;
+    let mint = StateWithExtensions::<Mint>::unpack(&data)
+        .map_err(|_| ErrorCode::AccountDidNotDeserialize)?;
+    Ok(mint.base)
+}
+
+pub fn unpack_token_account(info: &AccountInfo) -> Result<TokenAccount> {
+    require!(is_token_program(info.owner), ErrorCode::AccountOwnedByWrongProgram);
+    let data = info.try_borrow_data()?;
+    let token_account = StateWithExtensions::<TokenAccount>::unpack(&data)
+        .map_err(|_| ErrorCode::AccountDidNotDeserialize)?;
+    Ok(token_account.base)
+}
+
+// Mint account size including any extensions (82 bytes if none)
+pub fn mint_space(extensions: &MintExtensions) -> usize {
+    ExtensionType::get_account_len::<Mint>(&extensions.extension_types())
+}
+
+// NOTE Extensions MUST be initialized after the mint account is
+// created but BEFORE initialize_mint2. No signer needed.
+pub fn initialize_mint_extensions<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, token::InitializeMint2<'info>>,
+    extensions: &MintExtensions,
+    authority: &Pubkey,
+) -> Result<()> {
+    let token_program_id = ctx.program.key;
+    let mint = ctx.accounts.mint.key;
+    let mut ixs = Vec::new();
+    if let Some(transfer_fee) = &extensions.transfer_fee {
+        ixs.push(transfer_fee::instruction::initialize_transfer_fee_config(
+            token_program_id,
+            mint,
+            Some(authority), // transfer fee config authority
+            Some(authority), // withdraw withheld authority
+            transfer_fee.basis_points,
+            transfer_fee.maximum_fee,
+        )?);
+    }
+    if extensions.non_transferable {
+        ixs.push(spl_token_2022::instruction::initialize_non_transferable_mint(
+            token_program_id,
+            mint,
+        )?);
+    }
+    if let Some(interest_rate) = extensions.interest_rate {
+        ixs.push(interest_bearing_mint::instruction::initialize(
+            token_program_id,
+            mint,
+            Some(*authority), // rate authority
+            interest_rate,
+        )?);
+    }
+    for ix in ixs {
+        invoke(&ix, std::slice::from_ref(&ctx.accounts.mint))?;
+    }
+    Ok(())
+}
+
+pub fn initialize_mint2<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, token::InitializeMint2<'info>>,
+    decimals: u8,
+    authority: &Pubkey,
+    freeze_authority: Option<&Pubkey>,
+) -> Result<()> {
+    let ix = spl_token_2022::instruction::initialize_mint2(
+        ctx.program.key,
+        ctx.accounts.mint.key,
+        authority,
+        freeze_authority,
+        decimals,
+    )?;
+    invoke_signed(&ix, &[ctx.accounts.mint], ctx.signer_seeds).map_err(Into::into)
+}
+
+pub fn mint_to<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, token::MintTo<'info>>,
+    amount: u64,
+) -> Result<()> {
+    let ix = spl_token_2022::instruction::mint_to(
+        ctx.program.key,
+        ctx.accounts.mint.key,
+        ctx.accounts.to.key,
+        ctx.accounts.authority.key,
+        &[],
+        amount,
+    )?;
+    invoke_signed(
+        &ix,
+        &[ctx.accounts.to, ctx.accounts.mint, ctx.accounts.authority],
+        ctx.signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+pub fn burn<'info>(ctx: CpiContext<'_, '_, '_, 'info, token::Burn<'info>>, amount: u64) -> Result<()> {
+    let ix = spl_token_2022::instruction::burn(
+        ctx.program.key,
+        ctx.accounts.from.key,
+        ctx.accounts.mint.key,
+        ctx.accounts.authority.key,
+        &[],
+        amount,
+    )?;
+    invoke_signed(
+        &ix,
+        &[ctx.accounts.from, ctx.accounts.mint, ctx.accounts.authority],
+        ctx.signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+pub fn freeze_account<'info>(ctx: CpiContext<'_, '_, '_, 'info, token::FreezeAccount<'info>>) -> Result<()> {
+    let ix = spl_token_2022::instruction::freeze_account(
+        ctx.program.key,
+        ctx.accounts.account.key,
+        ctx.accounts.mint.key,
+        ctx.accounts.authority.key,
+        &[],
+    )?;
+    invoke_signed(
+        &ix,
+        &[ctx.accounts.account, ctx.accounts.mint, ctx.accounts.authority],
+        ctx.signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+pub fn thaw_account<'info>(ctx: CpiContext<'_, '_, '_, 'info, token::ThawAccount<'info>>) -> Result<()> {
+    let ix = spl_token_2022::instruction::thaw_account(
+        ctx.program.key,
+        ctx.accounts.account.key,
+        ctx.accounts.mint.key,
+        ctx.accounts.authority.key,
+        &[],
+    )?;
+    invoke_signed(
+        &ix,
+        &[ctx.accounts.account, ctx.accounts.mint, ctx.accounts.authority],
+        ctx.signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+// NOTE Stands in for init_if_needed + associated_token::*, whose codegen
+// derives the ATA with the legacy Token program id only.
+// ctx.program is the Associated Token Program.
+pub fn create_associated_token_account_idempotent<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, anchor_spl::associated_token::Create<'info>>,
+) -> Result<()> {
+    let ix = spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+        ctx.accounts.payer.key,
+        ctx.accounts.authority.key,
+        ctx.accounts.mint.key,
+        ctx.accounts.token_program.key,
+    );
+    invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.payer,
+            ctx.accounts.associated_token,
+            ctx.accounts.authority,
+            ctx.accounts.mint,
+            ctx.accounts.system_program,
+            ctx.accounts.token_program,
+        ],
+        ctx.signer_seeds,
+    )
+    .map_err(Into::into)
+}
@@ -1,10 +1,16 @@
 // https://www.youtube.com/watch?v=c1GJ-13z6pE&list=PLUBKxx7QjtVnU3hkPc8GF1Jh4DE7cf4n1&index=8
 use {
-    anchor_lang::{prelude::*, system_program},
+    anchor_lang::{
+        prelude::*,
+        solana_program::{hash::hash, program_option::COption},
+        system_program,
+    },
     anchor_spl::{
         associated_token,
-        token::{self, Mint, TokenAccount},
+        metadata::{self, Metadata},
+        token::{self, spl_token::instruction::AuthorityType, Mint, TokenAccount},
     },
+    mpl_token_metadata::state::DataV2,
 };
 
 // TIL:
@@ -72,8 +78,37 @@ pub mod custom_spl_token {
     use super::*;
 
     // === NEW Approach using single PDA for both Mint and Mint authority ====
-    pub fn initialize_dapp_token_mint(ctx: Context<InitializeDappTokenMint>) -> Result<()> {
-        msg!("Dapp token mint initialized!");
+    // U: No longer a global singleton. Each mint is namespaced by
+    // (creator, name), e.g. one PDA-controlled token per game season,
+    // and a registry PDA records the decimals and creator.
+    pub fn initialize_dapp_token_mint(
+        ctx: Context<InitializeDappTokenMint>,
+        name: String,
+        decimals: u8,
+    ) -> Result<()> {
+        // NOTE Checked here, not as an account constraint: Anchor derives
+        // and creates the 'init' PDAs before non-init constraints run.
+        // The PDAs are seeded with hash(name), so any name derives fine.
+        require!(
+            DappTokenMintRegistry::is_valid_name(&name),
+            CustomSplTokenError::InvalidMintName
+        );
+
+        let dapp_token_mint_registry = DappTokenMintRegistry {
+            creator: ctx.accounts.creator.key(),
+            mint: ctx.accounts.mint.key(),
+            decimals,
+            bump: *ctx.bumps
+                .get("dapp_token_mint_registry")
+                .ok_or(CustomSplTokenError::BumpNotFound)?,
+            mint_bump: *ctx.bumps.get("mint").ok_or(CustomSplTokenError::BumpNotFound)?,
+            name_hash: DappTokenMintRegistry::hash_name(&name),
+            name,
+        };
+        ctx.accounts
+            .dapp_token_mint_registry
+            .set_inner(dapp_token_mint_registry.clone());
+        msg!("Dapp token mint initialized! {:?}", &dapp_token_mint_registry);
         Ok(())
     }
 
@@ -83,7 +118,7 @@ pub mod custom_spl_token {
             "Minting supply to user token account (signing via mint PDA seeds)..."
         );
 
-        require!(amount > 0, "Amount must be greater than 0");
+        require!(amount > 0, CustomSplTokenError::ZeroAmount);
         // Q: Is there mint.cap or mint.max_supply properties?
         // require!(
         //             ctx.accounts.mint.supply + amount <= ctx.accounts.mint.cap,
@@ -91,14 +126,12 @@ pub mod custom_spl_token {
         //         );
 
 
+        // U: Mint PDA seeds now come from the registry (creator + name)
         // Syntax 1: Build CpiContext via impl fn
         // NOTE mint_to() is a helper function for MintTo IX
         token::mint_to(
             ctx.accounts.mint_to_cpi_ctx().with_signer(
-                &[&[
-                    "dapp-token-mint".as_bytes(),
-                    &[*ctx.bumps.get("mint").expect("Bump not found.")]
-                ]]
+                &[&ctx.accounts.dapp_token_mint_registry.mint_seeds()]
             ),
             amount
         )?;
@@ -173,7 +206,7 @@ pub mod custom_spl_token {
             // NOTE bumps.get("account_name"), NOT seed!
             *ctx.bumps
                 .get("dapp_token_manager_v1")
-                .expect("Bump not found."),
+                .ok_or(CustomSplTokenError::BumpNotFound)?,
         );
         // Update the inner account data
         // Q: clone() or no?
@@ -378,18 +411,26 @@ pub mod custom_spl_token {
 
 
     // -------------- Version2: Program ONLY Version2 (Fail) --------------
-    pub fn initialize_dapp_token_manager_v2(ctx: Context<InitializeDappTokenManager>, token_mint: Pubkey) -> Result<()> {
+    // U: Finished! Flow is:
+    // 1. initialize_dapp_token_manager_v2(): singleton DTM PDA, no mint yet
+    // 2. initialize_dapp_token_mint_v2(): DTM adopts a new Mint (once)
+    // 3. mint_dapp_token_supply_v2(): DTM PDA signs mint_to()
+    pub fn initialize_dapp_token_manager_v2(ctx: Context<InitializeDappTokenManager>) -> Result<()> {
         msg!("1. Create dapp_token_manager_v2 PDA account...");
+        // U: FIXED args were passed as (authority, token_mint) into
+        // new(mint, authority, ..), so the fields got swapped.
+        // U: Removed the 'token_mint' IX arg too. It was stored unchecked,
+        // so now the mint is adopted in initialize_dapp_token_mint_v2()
+        // once it actually exists.
         let dapp_token_manager_v2 = DappTokenManagerV2::new(
-            ctx.accounts.authority.key(),
             // NOTE Can't use ctx.accounts.mint.key() since the
-            // mint is not initialized at this point. I can still
-            // pass in IX arg data
-            token_mint,
+            // mint is not initialized at this point.
+            Pubkey::default(), // mint (not adopted yet)
+            ctx.accounts.authority.key(),
             // NOTE bumps.get("account_name"), NOT seed!
             *ctx.bumps
                 .get("dapp_token_manager_v2")
-                .expect("Bump not found."),
+                .ok_or(CustomSplTokenError::BumpNotFound)?,
         );
 
 
@@ -413,18 +454,46 @@ pub mod custom_spl_token {
         //     ctx.accounts.mint.mint_authority,
         //     ctx.accounts.dapp_token_manager_v2.key()
         // );
+        // U: 'init' + mint:: constraints create the Mint with the DTM as
+        // mint/freeze authority. We just need the DTM to adopt it.
+        ctx.accounts.dapp_token_manager_v2.mint = ctx.accounts.mint.key();
+        msg!("DappTokenManagerV2 adopted mint: {}", ctx.accounts.mint.key());
 
-       Ok(()) 
+        Ok(())
     }
 
     pub fn mint_dapp_token_supply_v2(ctx: Context<MintDappTokenSupplyV2>) -> Result<()> {
         // U: Stopped this approach. Read above.
+        // U: Picked it back up. Same as V1 except the DTM PDA seeds don't
+        // include the mint, and the ATA is init_if_needed.
+        msg!("1. Minting supply to user token account (signing via dapp_token_manager_v2 PDA)...");
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::MintTo {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.user_token_account.to_account_info(),
+                    authority: ctx.accounts.dapp_token_manager_v2.to_account_info(),
+                },
+                &[&[
+                    DappTokenManagerV2::SEED_PREFIX.as_bytes(),
+                    &[ctx.accounts.dapp_token_manager_v2.bump],
+                ]],
+            ),
+            DappTokenManagerV2::MINT_AMOUNT_RAW,
+        )?;
+
+        ctx.accounts.dapp_token_manager_v2.total_user_mint_count += 1;
+
         Ok(())
     }
 
 
     // -------- Version3: Combo of V1+V2 --------
-    pub fn initialize_dapp_token_manager_and_token_mint_v3(ctx: Context<InitializeDappTokenManagerAndTokenMintV3>) -> Result<()> {
+    pub fn initialize_dapp_token_manager_and_token_mint_v3(
+        ctx: Context<InitializeDappTokenManagerAndTokenMintV3>,
+        token_metadata: Option<TokenMetadataArgs>,
+    ) -> Result<()> {
         // 1. Create system account for mint
         msg!("1. Creating system account for the actual mint (token)...");
         system_program::create_account(
@@ -458,7 +527,7 @@ pub mod custom_spl_token {
             ctx.accounts.mint.key(),
             ctx.accounts.authority.key(),
             // NOTE bumps.get("account_name"), NOT seed!
-            *ctx.bumps.get("dapp_token_manager_v3").expect("Bump not found."),
+            *ctx.bumps.get("dapp_token_manager_v3").ok_or(CustomSplTokenError::BumpNotFound)?,
         );
         // Update the inner account data (serialize back?)
         ctx.accounts.dapp_token_manager_v3.set_inner(dapp_token_manager_v3.clone());
@@ -484,6 +553,64 @@ pub mod custom_spl_token {
             Some(&ctx.accounts.dapp_token_manager_v3.key()), // freeze authority
         )?;
 
+        // 4. (Optional) Create Metaplex metadata with DappTokenManagerV3 as
+        // both the signing mint authority and the update authority
+        if let Some(token_metadata) = token_metadata {
+            msg!("4. Creating token metadata account (signing via dapp_token_manager_v3 PDA seeds)...");
+            metadata::create_metadata_accounts_v3(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_metadata_program.to_account_info(),
+                    metadata::CreateMetadataAccountsV3 {
+                        metadata: ctx.accounts.metadata.to_account_info(),
+                        mint: ctx.accounts.mint.to_account_info(),
+                        mint_authority: ctx.accounts.dapp_token_manager_v3.to_account_info(),
+                        payer: ctx.accounts.authority.to_account_info(),
+                        update_authority: ctx.accounts.dapp_token_manager_v3.to_account_info(),
+                        system_program: ctx.accounts.system_program.to_account_info(),
+                        rent: ctx.accounts.rent.to_account_info(),
+                    },
+                    // Signer Seeds
+                    &[&[
+                        DappTokenManagerV3::SEED_PREFIX.as_bytes(),
+                        ctx.accounts.mint.key().as_ref(),
+                        &[ctx.accounts.dapp_token_manager_v3.bump],
+                    ]],
+                ),
+                token_metadata.to_data_v2(),
+                true, // is_mutable
+                true, // update_authority_is_signer
+                None, // collection details
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn update_dapp_token_metadata_v3(
+        ctx: Context<UpdateDappTokenMetadataV3>,
+        token_metadata: TokenMetadataArgs,
+    ) -> Result<()> {
+        msg!("Updating token metadata (signing via dapp_token_manager_v3 PDA seeds)...");
+        metadata::update_metadata_accounts_v2(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_metadata_program.to_account_info(),
+                metadata::UpdateMetadataAccountsV2 {
+                    metadata: ctx.accounts.metadata.to_account_info(),
+                    update_authority: ctx.accounts.dapp_token_manager_v3.to_account_info(),
+                },
+                // Signer Seeds
+                &[&[
+                    DappTokenManagerV3::SEED_PREFIX.as_bytes(),
+                    ctx.accounts.dapp_token_manager_v3.mint.as_ref(),
+                    &[ctx.accounts.dapp_token_manager_v3.bump],
+                ]],
+            ),
+            None, // new_update_authority (keep dapp_token_manager_v3)
+            Some(token_metadata.to_data_v2()),
+            None, // primary_sale_happened
+            None, // is_mutable
+        )?;
+
         Ok(())
     }
 
@@ -524,6 +651,329 @@ pub mod custom_spl_token {
 
         Ok(())
     }
+
+
+    // -------- Unified: one versioned DappTokenManager --------
+    // U: V1, V2 and V3 were the same struct with a different SEED_PREFIX,
+    // each with its own copy of every handler. Going forward there is a
+    // single DappTokenManager (seeds: ["dapp-token-manager", mint]) and the
+    // handlers below. The V1-V3 handlers stay so already-deployed mints can
+    // be moved over with migrate_manager().
+    // U: Works with legacy Token or Token-2022 (see token_interface).
+    // Token-2022 mints can opt into extensions at creation time.
+    pub fn initialize_manager(
+        ctx: Context<InitializeManager>,
+        token_metadata: Option<TokenMetadataArgs>,
+        token_extensions: Option<TokenExtensionsArgs>,
+    ) -> Result<()> {
+        let token_program_id = ctx.accounts.token_program.key();
+        let token_extensions =
+            token_interface::MintExtensions::from(&token_extensions.unwrap_or_default());
+        if !token_extensions.extension_types().is_empty() {
+            require_keys_eq!(
+                token_program_id,
+                spl_token_2022::ID,
+                CustomSplTokenError::ExtensionsRequireToken2022
+            );
+        }
+        // NOTE Token Metadata (pinned ~1.8.3) only supports legacy Token mints
+        if token_metadata.is_some() {
+            require_keys_eq!(
+                token_program_id,
+                token::ID,
+                CustomSplTokenError::MetadataRequiresLegacyToken
+            );
+        }
+
+        msg!("1. Creating system account for the actual mint (token)...");
+        let mint_space = token_interface::mint_space(&token_extensions);
+        system_program::create_account(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::CreateAccount {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: ctx.accounts.mint.to_account_info(),
+                },
+            ),
+            // U: V1/V3 hardcoded 10000000 lamports. Use rent-exempt minimum instead
+            ctx.accounts.rent.minimum_balance(mint_space), // lamports
+            mint_space as u64, // size (82 bytes + any extensions)
+            &token_program_id, // owner - Token Program (legacy or 2022) owns
+        )?;
+
+        msg!("2. Create DappTokenManager + mint PDA...");
+        let dapp_token_manager = DappTokenManager::new(
+            ctx.accounts.mint.key(),
+            ctx.accounts.authority.key(),
+            token_program_id,
+            *ctx.bumps.get("dapp_token_manager").ok_or(CustomSplTokenError::BumpNotFound)?,
+            0, // migrated_from (created fresh)
+        );
+        ctx.accounts.dapp_token_manager.set_inner(dapp_token_manager.clone());
+        msg!("DappTokenManager: {:?}", &dapp_token_manager);
+
+        // NOTE Extensions MUST be initialized before the mint itself
+        if !token_extensions.extension_types().is_empty() {
+            msg!("3a. Initializing Token-2022 mint extensions: {:?}", &token_extensions);
+            token_interface::initialize_mint_extensions(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    token::InitializeMint2 {
+                        mint: ctx.accounts.mint.to_account_info(),
+                    },
+                ),
+                &token_extensions,
+                &ctx.accounts.dapp_token_manager.key(), // extension authority
+            )?;
+        }
+
+        msg!("3. Initializing mint with dapp_token_manager as mint/freeze authority...");
+        token_interface::initialize_mint2(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::InitializeMint2 {
+                    mint: ctx.accounts.mint.to_account_info(),
+                },
+                &[&dapp_token_manager.seeds()],
+            ),
+            DappTokenManager::DECIMALS,
+            &ctx.accounts.dapp_token_manager.key(), // mint authority
+            Some(&ctx.accounts.dapp_token_manager.key()), // freeze authority
+        )?;
+
+        if let Some(token_metadata) = token_metadata {
+            // NOTE metadata/token_metadata_program are optional accounts,
+            // so the seeds check that used to be on 'metadata' is done here
+            let (metadata_account, token_metadata_program) = match (
+                &ctx.accounts.metadata,
+                &ctx.accounts.token_metadata_program,
+            ) {
+                (Some(metadata_account), Some(token_metadata_program)) => {
+                    (metadata_account, token_metadata_program)
+                }
+                _ => return err!(CustomSplTokenError::MetadataAccountsRequired),
+            };
+            let (metadata_pda, _) = Pubkey::find_program_address(
+                &[
+                    TokenMetadataArgs::SEED_PREFIX.as_ref(),
+                    token_metadata_program.key().as_ref(),
+                    ctx.accounts.mint.key().as_ref(),
+                ],
+                &token_metadata_program.key(),
+            );
+            require_keys_eq!(
+                metadata_account.key(),
+                metadata_pda,
+                CustomSplTokenError::InvalidMetadataAccount
+            );
+
+            msg!("4. Creating token metadata account (signing via dapp_token_manager PDA seeds)...");
+            metadata::create_metadata_accounts_v3(
+                CpiContext::new_with_signer(
+                    token_metadata_program.to_account_info(),
+                    metadata::CreateMetadataAccountsV3 {
+                        metadata: metadata_account.to_account_info(),
+                        mint: ctx.accounts.mint.to_account_info(),
+                        mint_authority: ctx.accounts.dapp_token_manager.to_account_info(),
+                        payer: ctx.accounts.authority.to_account_info(),
+                        update_authority: ctx.accounts.dapp_token_manager.to_account_info(),
+                        system_program: ctx.accounts.system_program.to_account_info(),
+                        rent: ctx.accounts.rent.to_account_info(),
+                    },
+                    &[&dapp_token_manager.seeds()],
+                ),
+                token_metadata.to_data_v2(),
+                true, // is_mutable
+                true, // update_authority_is_signer
+                None, // collection details
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn update_manager_metadata(
+        ctx: Context<UpdateManagerMetadata>,
+        token_metadata: TokenMetadataArgs,
+    ) -> Result<()> {
+        msg!("Updating token metadata (signing via dapp_token_manager PDA seeds)...");
+        metadata::update_metadata_accounts_v2(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_metadata_program.to_account_info(),
+                metadata::UpdateMetadataAccountsV2 {
+                    metadata: ctx.accounts.metadata.to_account_info(),
+                    update_authority: ctx.accounts.dapp_token_manager.to_account_info(),
+                },
+                &[&ctx.accounts.dapp_token_manager.seeds()],
+            ),
+            None, // new_update_authority (keep dapp_token_manager)
+            Some(token_metadata.to_data_v2()),
+            None, // primary_sale_happened
+            None, // is_mutable
+        )?;
+
+        Ok(())
+    }
+
+    pub fn mint_manager_supply(ctx: Context<MintManagerSupply>) -> Result<()> {
+        // NOTE Replaces init_if_needed, which only derives legacy Token ATAs
+        token_interface::create_associated_token_account_idempotent(CpiContext::new(
+            ctx.accounts.associated_token_program.to_account_info(),
+            associated_token::Create {
+                payer: ctx.accounts.minter.to_account_info(),
+                associated_token: ctx.accounts.user_token_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+        ))?;
+
+        msg!("1. Minting supply to the token account (signing via dapp_token_manager PDA seeds)...");
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::MintTo {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.user_token_account.to_account_info(),
+                    authority: ctx.accounts.dapp_token_manager.to_account_info(),
+                },
+                &[&ctx.accounts.dapp_token_manager.seeds()],
+            ),
+            DappTokenManager::MINT_AMOUNT_RAW,
+        )?;
+
+        let dapp_token_manager = &mut ctx.accounts.dapp_token_manager;
+        dapp_token_manager.total_user_mint_count = dapp_token_manager
+            .total_user_mint_count
+            .checked_add(1)
+            .ok_or(CustomSplTokenError::MathOverflow)?;
+
+        Ok(())
+    }
+
+    // NOTE Moves mint + freeze authority from an existing V1, V2 or V3 PDA
+    // to the unified DappTokenManager for the same mint. The legacy account
+    // is left in place, but can no longer mint since it's not the authority.
+    // U: V2 is a singleton, so its PDA seeds don't include the mint.
+    // U: A V3 mint's Metaplex update authority stays with the V3 PDA, so
+    // keep using update_dapp_token_metadata_v3() for those.
+    pub fn migrate_manager(ctx: Context<MigrateManager>) -> Result<()> {
+        let legacy_dapp_token_manager = &ctx.accounts.legacy_dapp_token_manager;
+        require_keys_eq!(
+            *legacy_dapp_token_manager.owner,
+            *ctx.program_id,
+            CustomSplTokenError::NotLegacyManager
+        );
+
+        // Q: How to tell V1/V2/V3 apart if the structs are identical?
+        // A: The 8-byte discriminator is the hash of the struct NAME,
+        // so try_deserialize() only succeeds for the matching type.
+        // NOTE Scoped so the data borrow is released before the CPIs
+        let (migrated_from, seed_prefix, mint, authority, total_user_mint_count, bump) = {
+            let data = legacy_dapp_token_manager.try_borrow_data()?;
+            if let Ok(v1) = DappTokenManagerV1::try_deserialize(&mut &data[..]) {
+                (1, DappTokenManagerV1::SEED_PREFIX, v1.mint, v1.authority, v1.total_user_mint_count, v1.bump)
+            } else if let Ok(v2) = DappTokenManagerV2::try_deserialize(&mut &data[..]) {
+                (2, DappTokenManagerV2::SEED_PREFIX, v2.mint, v2.authority, v2.total_user_mint_count, v2.bump)
+            } else if let Ok(v3) = DappTokenManagerV3::try_deserialize(&mut &data[..]) {
+                (3, DappTokenManagerV3::SEED_PREFIX, v3.mint, v3.authority, v3.total_user_mint_count, v3.bump)
+            } else {
+                return err!(CustomSplTokenError::NotLegacyManager);
+            }
+        };
+        require_keys_eq!(mint, ctx.accounts.mint.key(), CustomSplTokenError::MintMismatch);
+        require_keys_eq!(authority, ctx.accounts.authority.key(), CustomSplTokenError::Unauthorized);
+
+        let bump_seed = [bump];
+        let legacy_seeds: Vec<&[u8]> = if migrated_from == 2 {
+            vec![seed_prefix.as_bytes(), &bump_seed]
+        } else {
+            vec![seed_prefix.as_bytes(), mint.as_ref(), &bump_seed]
+        };
+        let legacy_seeds: &[&[u8]] = &legacy_seeds;
+        let legacy_address = Pubkey::create_program_address(legacy_seeds, ctx.program_id)
+            .map_err(|_| CustomSplTokenError::NotLegacyManager)?;
+        require_keys_eq!(
+            legacy_address,
+            legacy_dapp_token_manager.key(),
+            CustomSplTokenError::NotLegacyManager
+        );
+        require!(
+            ctx.accounts.mint.mint_authority == COption::Some(legacy_address),
+            CustomSplTokenError::MintAuthorityMismatch
+        );
+
+        msg!("1. Moving mint authority from V{} PDA to dapp_token_manager...", migrated_from);
+        token::set_authority(
+            ctx.accounts.set_authority_cpi_ctx().with_signer(&[legacy_seeds]),
+            AuthorityType::MintTokens,
+            Some(ctx.accounts.dapp_token_manager.key()),
+        )?;
+
+        // NOTE Only move freeze authority if the legacy PDA actually holds it
+        if ctx.accounts.mint.freeze_authority == COption::Some(legacy_address) {
+            msg!("2. Moving freeze authority from V{} PDA to dapp_token_manager...", migrated_from);
+            token::set_authority(
+                ctx.accounts.set_authority_cpi_ctx().with_signer(&[legacy_seeds]),
+                AuthorityType::FreezeAccount,
+                Some(ctx.accounts.dapp_token_manager.key()),
+            )?;
+        }
+
+        let mut dapp_token_manager = DappTokenManager::new(
+            mint,
+            authority,
+            token::ID, // V1-V3 mints are all legacy Token
+            *ctx.bumps.get("dapp_token_manager").ok_or(CustomSplTokenError::BumpNotFound)?,
+            migrated_from,
+        );
+        // Carry over the count so we don't lose history
+        dapp_token_manager.total_user_mint_count = total_user_mint_count;
+        ctx.accounts.dapp_token_manager.set_inner(dapp_token_manager.clone());
+        msg!("DappTokenManager: {:?}", &dapp_token_manager);
+
+        Ok(())
+    }
+}
+
+// NOTE Anchor assigns custom error codes starting at 6000,
+// in declaration order. Only append new variants to keep the
+// numbers stable for clients.
+#[error_code]
+pub enum CustomSplTokenError {
+    #[msg("Amount must be greater than 0")]
+    ZeroAmount,
+    #[msg("Mint does not match the dapp token manager's mint")]
+    MintMismatch,
+    #[msg("Bump not found")]
+    BumpNotFound,
+    #[msg("Signer is not authorized for this dapp token manager")]
+    Unauthorized,
+    #[msg("Dapp token manager has already adopted a mint")]
+    MintAlreadyAdopted,
+    #[msg("Math overflow")]
+    MathOverflow,
+    #[msg("Account is not a V1, V2 or V3 dapp token manager")]
+    NotLegacyManager,
+    #[msg("Legacy dapp token manager is not the mint authority")]
+    MintAuthorityMismatch,
+    #[msg("Mint name must be 1 to 32 bytes")]
+    InvalidMintName,
+    #[msg("Token program must be the legacy Token program or Token-2022")]
+    UnsupportedTokenProgram,
+    #[msg("Token program does not match the dapp token manager's token program")]
+    TokenProgramMismatch,
+    #[msg("Token extensions require the Token-2022 program")]
+    ExtensionsRequireToken2022,
+    #[msg("Metaplex token metadata requires the legacy Token program")]
+    MetadataRequiresLegacyToken,
+    #[msg("Token account is not the user's associated token account for this mint")]
+    InvalidTokenAccount,
+    #[msg("Token metadata requires the metadata account and Token Metadata program")]
+    MetadataAccountsRequired,
+    #[msg("Metadata account is not the mint's Metaplex metadata PDA")]
+    InvalidMetadataAccount,
 }
 
 // ===== NEW Approach using PDA for both Mint and Mint authority ===
@@ -533,22 +983,44 @@ pub mod custom_spl_token {
 // enables our program to sign for the minting of the tokens.
 // In order to initialize the Mint account, we'll need to include the 
 // token_program, rent, and system_program in the list of accounts.
+// U: Seeds are now ["dapp-token-mint", creator, hash(name)] instead of the
+// global ["dapp-token-mint"], so a creator can have many PDA mints.
+// NOTE The name is hashed so its length never limits seed derivation.
+// It's validated in initialize_dapp_token_mint().
 #[derive(Accounts)]
+#[instruction(name: String, decimals: u8)]
 pub struct InitializeDappTokenMint<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = DappTokenMintRegistry::ACCOUNT_SPACE,
+        seeds = [
+            DappTokenMintRegistry::SEED_PREFIX.as_bytes(),
+            creator.key().as_ref(),
+            DappTokenMintRegistry::hash_name(&name).as_ref(),
+        ],
+        bump
+    )]
+    pub dapp_token_mint_registry: Account<'info, DappTokenMintRegistry>,
+
     #[account(
         init,
-        seeds = ["dapp-token-mint".as_bytes()],
+        seeds = [
+            DappTokenMintRegistry::MINT_SEED_PREFIX.as_bytes(),
+            creator.key().as_ref(),
+            DappTokenMintRegistry::hash_name(&name).as_ref(),
+        ],
         bump,
-        payer = user,
-        mint::decimals = 9,
+        payer = creator,
+        mint::decimals = decimals,
         mint::authority = mint,
         mint::freeze_authority = mint,
     )]
     pub mint: Account<'info, Mint>,
 
-    #[account(mut)]
-    pub user: Signer<'info>,
-
     pub token_program: Program<'info, token::Token>,
     pub rent: Sysvar<'info, Rent>,
     pub system_program: Program<'info, System>
@@ -563,14 +1035,19 @@ pub struct MintDappTokenSupply<'info> {
     // Q: Need this with init_if_needed? In case user_token_account doesn't exist yet, I think
     // I need the user to sign and pay
     // A: Yes!
+    // U: Only the registry's creator can mint now, otherwise anyone could
+    // inflate any creator's namespaced mint. The creator pays for the ATA.
     #[account(mut)]
-    pub user: Signer<'info>, 
+    pub creator: Signer<'info>,
+
+    /// CHECK: Owner of user_token_account, which receives the supply
+    pub user: UncheckedAccount<'info>,
 
     // U: Bare would use `init_if_needed` instead of creating from Client
     // NOTE Need to add features = ["init-if-needed"] in Cargo.toml
     #[account(
         init_if_needed,
-        payer = user,
+        payer = creator,
         associated_token::mint = mint,
         associated_token::authority = user
     )]
@@ -579,16 +1056,33 @@ pub struct MintDappTokenSupply<'info> {
     // IMPORTANT: MUST make the 'mint' account writable since supply will be mutated!
     // U: By using a PDA for Mint and Mint.authority, we need
     // to use its seeds and bump to retrieve.
+    // U: Seeds (creator + name) and bump come from the registry now
     #[account(
         mut,
-        seeds = ["dapp-token-mint".as_bytes()],
-        bump,
+        seeds = [
+            DappTokenMintRegistry::MINT_SEED_PREFIX.as_bytes(),
+            dapp_token_mint_registry.creator.as_ref(),
+            dapp_token_mint_registry.name_hash.as_ref(),
+        ],
+        bump = dapp_token_mint_registry.mint_bump,
         // Q: Can I check that supply > amount?
         // A: Nope! May have another approach...
         // constraint = mint.supply > amount
     )]
     pub mint: Account<'info, Mint>,
 
+    #[account(
+        has_one = mint @ CustomSplTokenError::MintMismatch,
+        has_one = creator @ CustomSplTokenError::Unauthorized,
+        seeds = [
+            DappTokenMintRegistry::SEED_PREFIX.as_bytes(),
+            dapp_token_mint_registry.creator.as_ref(),
+            dapp_token_mint_registry.name_hash.as_ref(),
+        ],
+        bump = dapp_token_mint_registry.bump
+    )]
+    pub dapp_token_mint_registry: Account<'info, DappTokenMintRegistry>,
+
     pub rent: Sysvar<'info, Rent>,
     pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
     pub token_program: Program<'info, token::Token>,
@@ -676,13 +1170,14 @@ pub struct MintDappSpl<'info> {
     // - DONE Bring in dapp_token_manager_v1 account
     // - DONE Bring in user (wallet) account
     // - DONE rent, system_program, token_program, associated_token
-    // - Determine the names for the user wallet (user, payer, authority) -- Choose one!
-    // - Build the actual instruction method
+    // - DONE Determine the names for the user wallet (user, payer, authority) -- Choose one!
+    //   U: 'minter', since it's either the DTM authority OR the ATA owner
+    // - DONE Build the actual instruction method
     // - Checks/constraints to consider:
     //   - Q: How to prevent one user getting all the supply?
-    //   - mint.key() == dapp_token_manager_v1.mint
-    //   - user_token_account.mint == dapp_token_manager_v1.mint
-    //   - user_token_account.owner == user.key()
+    //   - DONE mint.key() == dapp_token_manager_v1.mint
+    //   - DONE user_token_account.mint == dapp_token_manager_v1.mint
+    //   - DONE user_token_account.owner == user.key() (or DTM authority signs)
     //   - mint.mint_authority == dapp_token_manager_v1
     //   - mint.freeze_authority == dapp_token_manager_v1
     //   - mint.supply < mint.cap
@@ -699,7 +1194,7 @@ pub struct MintDappSpl<'info> {
     // U: MUST make the 'mint' account writable since supply will be mutated!
     #[account(
         mut,
-        constraint = mint.key() == dapp_token_manager_v1.mint
+        constraint = mint.key() == dapp_token_manager_v1.mint @ CustomSplTokenError::MintMismatch
     )]
     pub mint: Account<'info, Mint>,
 
@@ -722,7 +1217,7 @@ pub struct MintDappSpl<'info> {
     // REF: Escrow program tests 'buyer_z_token_account'
     #[account(
         mut,
-        constraint = user_token_account.mint == mint.key(),
+        constraint = user_token_account.mint == mint.key() @ CustomSplTokenError::MintMismatch,
         // Q: What happens if I remove user input account and
         // this constraint? If I have user & this constraint,
         // I encounter TokenAccountNotFoundError. If I don't pass
@@ -731,9 +1226,19 @@ pub struct MintDappSpl<'info> {
         // U: I THINK I only need to pass user wallet (payer) and
         // add this constraint when doing TRANSFER...
         // constraint = user_token_account.owner == user.key(),
+        // U: Moved the owner check to 'minter' below
     )]
     pub user_token_account: Account<'info, TokenAccount>,
 
+    // NOTE Previously there was NO Signer, so anyone could mint to any
+    // token account of the mint. Now it must be either the DTM's recorded
+    // 'authority', or the user signing for their own token account.
+    #[account(
+        constraint = minter.key() == dapp_token_manager_v1.authority
+            || minter.key() == user_token_account.owner @ CustomSplTokenError::Unauthorized
+    )]
+    pub minter: Signer<'info>,
+
     pub rent: Sysvar<'info, Rent>,
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, token::Token>,
@@ -861,9 +1366,13 @@ pub struct InitializeDappTokenMintOldV2<'info> {
     // For example, I want to init DTManager and use mint.key()
     // as a seed. However, I can't because I haven't initiliazed
     // the Mint yet, so can't pass the 'mint' account.
+    // U: Can't require dapp_token_manager_v2.mint == mint.key() here,
+    // since this IX is what sets it. Instead, only the DTM authority
+    // may adopt a mint, and only once.
     #[account(
         mut,
-        constraint = dapp_token_manager_v2.mint == mint.key(),
+        has_one = authority @ CustomSplTokenError::Unauthorized,
+        constraint = dapp_token_manager_v2.mint == Pubkey::default() @ CustomSplTokenError::MintAlreadyAdopted,
         seeds = [
             DappTokenManagerV2::SEED_PREFIX.as_ref(),
             // Q: Can I access mint.key() since mint is 
@@ -900,13 +1409,13 @@ pub struct MintDappTokenSupplyV2<'info> {
     // IMPORTANT: MUST make the 'mint' account writable since supply will be mutated!
     #[account(
         mut,
-        constraint = mint.key() == dapp_token_manager_v2.mint
+        constraint = mint.key() == dapp_token_manager_v2.mint @ CustomSplTokenError::MintMismatch
     )]
     pub mint: Account<'info, Mint>,
 
     #[account(
         mut,
-        constraint = dapp_token_manager_v2.mint == mint.key(),
+        constraint = dapp_token_manager_v2.mint == mint.key() @ CustomSplTokenError::MintMismatch,
         seeds = [
             DappTokenManagerV2::SEED_PREFIX.as_ref(),
         ],
@@ -954,9 +1463,54 @@ pub struct InitializeDappTokenManagerAndTokenMintV3<'info> {
     #[account(mut)]
     pub authority: Signer<'info>, // The wallet (fee payer)
 
+    // NOTE Only written to when 'token_metadata' is Some
+    /// CHECK: Metaplex metadata PDA. Validated by seeds and created in CPI
+    #[account(
+        mut,
+        seeds = [
+            TokenMetadataArgs::SEED_PREFIX.as_ref(),
+            token_metadata_program.key().as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub metadata: UncheckedAccount<'info>,
+
     pub rent: Sysvar<'info, Rent>,
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, token::Token>,
+    pub token_metadata_program: Program<'info, Metadata>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateDappTokenMetadataV3<'info> {
+    #[account(
+        has_one = authority,
+        seeds = [
+            DappTokenManagerV3::SEED_PREFIX.as_ref(),
+            dapp_token_manager_v3.mint.as_ref(),
+        ],
+        bump = dapp_token_manager_v3.bump
+    )]
+    pub dapp_token_manager_v3: Account<'info, DappTokenManagerV3>,
+
+    /// CHECK: Metaplex metadata PDA. Validated by seeds and in CPI
+    #[account(
+        mut,
+        seeds = [
+            TokenMetadataArgs::SEED_PREFIX.as_ref(),
+            token_metadata_program.key().as_ref(),
+            dapp_token_manager_v3.mint.as_ref(),
+        ],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub metadata: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+
+    pub token_metadata_program: Program<'info, Metadata>,
 }
 
 
@@ -975,13 +1529,13 @@ pub struct MintDappTokenSupplyV3<'info> {
     // IMPORTANT: MUST make the 'mint' account writable since supply will be mutated!
     #[account(
         mut,
-        constraint = mint.key() == dapp_token_manager_v3.mint
+        constraint = mint.key() == dapp_token_manager_v3.mint @ CustomSplTokenError::MintMismatch
     )]
     pub mint: Account<'info, Mint>,
 
     #[account(
         mut,
-        constraint = dapp_token_manager_v3.mint == mint.key(),
+        constraint = dapp_token_manager_v3.mint == mint.key() @ CustomSplTokenError::MintMismatch,
         seeds = [
             DappTokenManagerV3::SEED_PREFIX.as_ref(),
             mint.key().as_ref(),
@@ -1002,6 +1556,229 @@ pub struct MintDappTokenSupplyV3<'info> {
 }
 
 
+// ----------- Unified: versioned DappTokenManager ----------
+#[derive(Accounts)]
+pub struct InitializeManager<'info> {
+    // Client: Need to pass a Keypair
+    #[account(mut)]
+    pub mint: Signer<'info>,
+    #[account(
+        init,
+        payer = authority,
+        space = DappTokenManager::ACCOUNT_SPACE,
+        seeds = [
+            DappTokenManager::SEED_PREFIX.as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump
+    )]
+    pub dapp_token_manager: Account<'info, DappTokenManager>,
+    #[account(mut)]
+    pub authority: Signer<'info>, // The wallet (fee payer)
+    // NOTE Only needed (and then required) when 'token_metadata' is Some
+    // U: Optional account, so the PDA is checked in the handler instead
+    // of with seeds (seeds::program can't use the optional program)
+    /// CHECK: Metaplex metadata PDA. Validated in handler and created in CPI
+    #[account(mut)]
+    pub metadata: Option<UncheckedAccount<'info>>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+    // U: Either Token program. The DTM records which one owns the mint.
+    /// CHECK: Must be the legacy Token program or Token-2022
+    #[account(
+        constraint = token_interface::is_token_program(token_program.key)
+            @ CustomSplTokenError::UnsupportedTokenProgram
+    )]
+    pub token_program: UncheckedAccount<'info>,
+    pub token_metadata_program: Option<Program<'info, Metadata>>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateManagerMetadata<'info> {
+    #[account(
+        has_one = authority @ CustomSplTokenError::Unauthorized,
+        seeds = [
+            DappTokenManager::SEED_PREFIX.as_ref(),
+            dapp_token_manager.mint.as_ref(),
+        ],
+        bump = dapp_token_manager.bump
+    )]
+    pub dapp_token_manager: Account<'info, DappTokenManager>,
+    /// CHECK: Metaplex metadata PDA. Validated by seeds and in CPI
+    #[account(
+        mut,
+        seeds = [
+            TokenMetadataArgs::SEED_PREFIX.as_ref(),
+            token_metadata_program.key().as_ref(),
+            dapp_token_manager.mint.as_ref(),
+        ],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub metadata: UncheckedAccount<'info>,
+    pub authority: Signer<'info>,
+    pub token_metadata_program: Program<'info, Metadata>,
+}
+
+#[derive(Accounts)]
+pub struct MintManagerSupply<'info> {
+    // U: UncheckedAccount (was init_if_needed Account<TokenAccount>) so it
+    // can be a Token-2022 ATA. Created if needed in mint_manager_supply()
+    /// CHECK: User's ATA for (mint, token_program). Address validated here
+    #[account(
+        mut,
+        address = token_interface::get_associated_token_address(
+            &user.key(),
+            &mint.key(),
+            &token_program.key(),
+        ) @ CustomSplTokenError::InvalidTokenAccount
+    )]
+    pub user_token_account: UncheckedAccount<'info>,
+    // IMPORTANT: MUST make the 'mint' account writable since supply will be mutated!
+    /// CHECK: Legacy Token or Token-2022 mint. A seed of dapp_token_manager
+    #[account(mut)]
+    pub mint: UncheckedAccount<'info>,
+    // NOTE mint is a seed, so the PDA check alone ties the manager to this mint
+    #[account(
+        mut,
+        seeds = [
+            DappTokenManager::SEED_PREFIX.as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump = dapp_token_manager.bump
+    )]
+    pub dapp_token_manager: Account<'info, DappTokenManager>,
+    /// CHECK: Owner of user_token_account. Only signs when it's also the 'minter'
+    pub user: UncheckedAccount<'info>,
+    // NOTE Same rule as mint_dapp_spl(): either the DTM's recorded
+    // 'authority', or the user signing for their own ATA.
+    // Pays for the ATA if it doesn't exist yet.
+    #[account(
+        mut,
+        constraint = minter.key() == dapp_token_manager.authority
+            || minter.key() == user.key() @ CustomSplTokenError::Unauthorized
+    )]
+    pub minter: Signer<'info>,
+    pub rent: Sysvar<'info, Rent>,
+    pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
+    /// CHECK: Must be the token program recorded on the DTM (legacy or 2022)
+    #[account(
+        address = dapp_token_manager.token_program @ CustomSplTokenError::TokenProgramMismatch
+    )]
+    pub token_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateManager<'info> {
+    // IMPORTANT: MUST be writable since its authorities are changing
+    #[account(mut)]
+    pub mint: Account<'info, Mint>,
+    // Q: Why not Account<DappTokenManagerV1>?
+    // A: Could be V1, V2 or V3, so it's checked by hand in migrate_manager()
+    /// CHECK: Owner, discriminator, PDA and authority are validated in migrate_manager()
+    pub legacy_dapp_token_manager: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = authority,
+        space = DappTokenManager::ACCOUNT_SPACE,
+        seeds = [
+            DappTokenManager::SEED_PREFIX.as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump
+    )]
+    pub dapp_token_manager: Account<'info, DappTokenManager>,
+    // NOTE Must be the legacy DTM's authority
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, token::Token>,
+}
+
+impl<'info> MigrateManager<'info> {
+    fn set_authority_cpi_ctx(&self) -> CpiContext<'_, '_, '_, 'info, token::SetAuthority<'info>> {
+        CpiContext::new(
+            self.token_program.to_account_info(),
+            token::SetAuthority {
+                current_authority: self.legacy_dapp_token_manager.to_account_info(),
+                account_or_mint: self.mint.to_account_info(),
+            },
+        )
+    }
+}
+
+// NOTE Instruction data (not an account) for the Metaplex metadata
+// account of a mint. Max lengths are enforced by the Token Metadata
+// Program (name 32, symbol 10, uri 200).
+// U: The logic lives in token_interface::TokenMetadata. This copy is
+// only here so the type shows up in the IDL (see From below).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct TokenMetadataArgs {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
+
+impl TokenMetadataArgs {
+    pub const SEED_PREFIX: &'static str = token_interface::TokenMetadata::SEED_PREFIX;
+
+    pub fn to_data_v2(&self) -> DataV2 {
+        token_interface::TokenMetadata::from(self).to_data_v2()
+    }
+}
+
+// NOTE Destructures every field, so this stops compiling if either
+// side gains or loses one
+impl From<&TokenMetadataArgs> for token_interface::TokenMetadata {
+    fn from(args: &TokenMetadataArgs) -> Self {
+        let TokenMetadataArgs { name, symbol, uri } = args;
+        Self {
+            name: name.clone(),
+            symbol: symbol.clone(),
+            uri: uri.clone(),
+        }
+    }
+}
+
+// NOTE Instruction data (not an account) for opting a new Token-2022
+// mint into extensions. Extensions can only be added when the mint is
+// created, so only initialize_manager() reads this. The DTM PDA is
+// the authority for each one.
+// U: Same as TokenMetadataArgs, the logic is in token_interface::MintExtensions
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct TokenExtensionsArgs {
+    pub transfer_fee: Option<TransferFeeArgs>,
+    pub non_transferable: bool,
+    pub interest_rate: Option<i16>, // basis points
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct TransferFeeArgs {
+    pub basis_points: u16,
+    pub maximum_fee: u64, // raw amount
+}
+
+impl From<&TokenExtensionsArgs> for token_interface::MintExtensions {
+    fn from(args: &TokenExtensionsArgs) -> Self {
+        let TokenExtensionsArgs {
+            transfer_fee,
+            non_transferable,
+            interest_rate,
+        } = args;
+        Self {
+            transfer_fee: transfer_fee.as_ref().map(
+                |TransferFeeArgs { basis_points, maximum_fee }| token_interface::TransferFee {
+                    basis_points: *basis_points,
+                    maximum_fee: *maximum_fee,
+                },
+            ),
+            non_transferable: *non_transferable,
+            interest_rate: *interest_rate,
+        }
+    }
+}
+
 // Version1
 // U: Adding another high-level account to enable multiple escrows created by same/single wallet
 // NOTE: Technically don't need to create a data account for the PDA. This is only if I want
@@ -1181,6 +1958,129 @@ impl DappTokenManagerV3 {
 }
 
 
+// NEW Approach: One registry per (creator, name) PDA mint namespace.
+// The mint is its own authority, so this just records who created it
+// and what it looks like, plus the bumps needed to sign for it.
+#[account]
+#[derive(Default, Debug)]
+pub struct DappTokenMintRegistry {
+    // 8 bytes for Discrimator
+    pub creator: Pubkey,     // 32 bytes
+    pub mint: Pubkey,        // 32 bytes
+    pub decimals: u8,        // 1 byte
+    pub bump: u8,            // 1 byte
+    pub mint_bump: u8,       // 1 byte
+    pub name_hash: [u8; 32], // 32 bytes PDA seed, see hash_name()
+    pub name: String,        // 4 + MAX_NAME_LENGTH bytes
+}
+
+const CREATOR_LENGTH: usize = 32; // Pubkey
+const DECIMALS_LENGTH: usize = 1; // u8
+const NAME_HASH_LENGTH: usize = 32; // [u8; 32]
+const STRING_LENGTH_PREFIX: usize = 4; // u32
+
+impl DappTokenMintRegistry {
+    // NOTE Bounds the account space. Seeds use hash_name(), not the name
+    pub const MAX_NAME_LENGTH: usize = 32;
+    pub const ACCOUNT_SPACE: usize = DISCRIMINATOR_LENGTH
+        + CREATOR_LENGTH
+        + MINT_LENGTH
+        + DECIMALS_LENGTH
+        + BUMP_LENGTH
+        + BUMP_LENGTH
+        + NAME_HASH_LENGTH
+        + STRING_LENGTH_PREFIX
+        + Self::MAX_NAME_LENGTH;
+
+    pub const SEED_PREFIX: &'static str = "dapp-token-mint-registry";
+    pub const MINT_SEED_PREFIX: &'static str = "dapp-token-mint";
+
+    pub fn is_valid_name(name: &str) -> bool {
+        !name.is_empty() && name.len() <= Self::MAX_NAME_LENGTH
+    }
+
+    // NOTE sha256 of the name. Always 32 bytes, so names of any length
+    // (even invalid ones) derive a PDA and reach the handler's check
+    pub fn hash_name(name: &str) -> [u8; 32] {
+        hash(name.as_bytes()).to_bytes()
+    }
+
+    pub fn mint_seeds(&self) -> [&[u8]; 4] {
+        [
+            Self::MINT_SEED_PREFIX.as_bytes(),
+            self.creator.as_ref(),
+            self.name_hash.as_ref(),
+            std::slice::from_ref(&self.mint_bump),
+        ]
+    }
+}
+
+// Unified: Replaces V1-V3, which only differed by SEED_PREFIX.
+// NOTE 'version' is the account layout version, so future changes
+// can be detected and migrated. 'migrated_from' records which legacy
+// DTM (1, 2 or 3) the mint came from, or 0 if created fresh.
+#[account]
+#[derive(Default, Debug)]
+pub struct DappTokenManager {
+    // 8 bytes for Discrimator
+    pub version: u8,                // 1 byte
+    pub migrated_from: u8,          // 1 byte
+    pub mint: Pubkey,               // 32 bytes
+    pub token_program: Pubkey,      // 32 bytes Legacy Token or Token-2022 (owns the mint)
+    pub authority: Pubkey,          // 32 bytes Initializer/Payer
+    pub total_user_mint_count: u64, // 8 bytes
+    pub bump: u8,                   // 1 byte
+}
+
+const TOKEN_PROGRAM_LENGTH: usize = 32; // Pubkey
+const VERSION_LENGTH: usize = 1; // u8
+const MIGRATED_FROM_LENGTH: usize = 1; // u8
+
+impl DappTokenManager {
+    pub const ACCOUNT_SPACE: usize = DISCRIMINATOR_LENGTH
+        + VERSION_LENGTH
+        + MIGRATED_FROM_LENGTH
+        + MINT_LENGTH
+        + TOKEN_PROGRAM_LENGTH
+        + AUTHORITY_LENGTH
+        + TOTAL_USER_MINT_COUNT_LENGTH
+        + BUMP_LENGTH;
+
+    pub const SEED_PREFIX: &'static str = "dapp-token-manager";
+    pub const CURRENT_VERSION: u8 = 1;
+    pub const DECIMALS: u8 = 9;
+    pub const MINT_AMOUNT_RAW: u64 = 1000000000 * 100; // 100 Tokens
+    pub const MINT_AMOUNT_UI: u64 = 100; // 100 Tokens
+
+    pub fn new(
+        mint: Pubkey,
+        authority: Pubkey,
+        token_program: Pubkey,
+        bump: u8,
+        migrated_from: u8,
+    ) -> Self {
+        DappTokenManager {
+            version: Self::CURRENT_VERSION,
+            migrated_from,
+            mint,
+            token_program,
+            authority,
+            total_user_mint_count: 0,
+            bump,
+        }
+    }
+
+    // U: FIXED the 'temporary value created' error from the V1/V3
+    // seeds helpers by borrowing the stored bump as a slice.
+    pub fn seeds(&self) -> [&[u8]; 3] {
+        [
+            Self::SEED_PREFIX.as_bytes(),
+            self.mint.as_ref(),
+            std::slice::from_ref(&self.bump),
+        ]
+    }
+}
+
 // #[cfg(test)]
 // mod tests {
 //     use super::*;
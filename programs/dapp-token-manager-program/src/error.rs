@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+// NOTE Anchor assigns custom error codes starting at 6000,
+// in declaration order. Only append new variants to keep the
+// numbers stable for clients.
+#[error_code]
+pub enum DappTokenManagerError {
+    #[msg("User must wait for the mint cooldown to elapse before minting again")]
+    MintCooldownNotElapsed,
+    #[msg("User has reached the maximum number of mints allowed")]
+    UserMintLimitReached,
+    #[msg("Minting this amount would exceed the manager's max supply")]
+    SupplyCapExceeded,
+    #[msg("Signer is not the pending authority for this manager")]
+    NotPendingAuthority,
+    #[msg("Minting is paused for this manager")]
+    Paused,
+    #[msg("Amount must be greater than 0")]
+    ZeroAmount,
+    #[msg("Mint does not match the manager's mint")]
+    MintMismatch,
+    #[msg("Signer is not the manager's authority")]
+    Unauthorized,
+    #[msg("Bump not found")]
+    BumpNotFound,
+    #[msg("Arithmetic overflow or underflow")]
+    MathOverflow,
+    #[msg("Token program must be the legacy Token program or Token-2022")]
+    UnsupportedTokenProgram,
+    #[msg("Token program does not match the manager's token program")]
+    TokenProgramMismatch,
+    #[msg("Token extensions require the Token-2022 program")]
+    ExtensionsRequireToken2022,
+    #[msg("Metaplex token metadata requires the legacy Token program")]
+    MetadataRequiresLegacyToken,
+    #[msg("Token account is not the user's associated token account for this mint")]
+    InvalidTokenAccount,
+    #[msg("Cooldown seconds must not be negative")]
+    InvalidCooldown,
+    #[msg("Token metadata requires the metadata account and Token Metadata program")]
+    MetadataAccountsRequired,
+    #[msg("Metadata account is not the mint's Metaplex metadata PDA")]
+    InvalidMetadataAccount,
+}
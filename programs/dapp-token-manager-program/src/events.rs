@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+
+// NOTE Events are written to the program logs (base64 "Program data:")
+// and can be decoded by clients using the IDL, e.g.:
+// program.addEventListener("DappTokenSupplyMinted", (event, slot) => {...})
+
+#[event]
+pub struct DappTokenManagerCreated {
+    pub dapp_token_manager: Pubkey,
+    pub mint: Pubkey,
+    pub token_program: Pubkey,
+    pub authority: Pubkey,
+    pub authority_payer: Pubkey,
+    pub decimals: u8,
+    pub supply_amount_per_mint: u64,
+    pub max_supply: u64,
+    pub cooldown_seconds: i64,
+    pub max_mints_per_user: u64,
+}
+
+#[event]
+pub struct DappTokenSupplyMinted {
+    pub dapp_token_manager: Pubkey,
+    pub mint: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub total_mint_count: u64,
+}
+
+#[event]
+pub struct DappTokenBurned {
+    pub dapp_token_manager: Pubkey,
+    pub mint: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub total_burned: u64,
+    pub circulating_supply: u64,
+}
+
+#[event]
+pub struct DappTokenAccountFrozen {
+    pub dapp_token_manager: Pubkey,
+    pub mint: Pubkey,
+    pub user_token_account: Pubkey,
+    pub owner: Pubkey,
+}
+
+#[event]
+pub struct DappTokenAccountThawed {
+    pub dapp_token_manager: Pubkey,
+    pub mint: Pubkey,
+    pub user_token_account: Pubkey,
+    pub owner: Pubkey,
+}
+
+#[event]
+pub struct DappTokenMetadataUpdated {
+    pub dapp_token_manager: Pubkey,
+    pub mint: Pubkey,
+    pub metadata: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
+
+#[event]
+pub struct DappTokenManagerUpdated {
+    pub dapp_token_manager: Pubkey,
+    pub authority: Pubkey,
+    pub supply_amount_per_mint: u64,
+    pub paused: bool,
+}
+
+#[event]
+pub struct DappTokenManagerAuthorityProposed {
+    pub dapp_token_manager: Pubkey,
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+#[event]
+pub struct DappTokenManagerAuthorityAccepted {
+    pub dapp_token_manager: Pubkey,
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
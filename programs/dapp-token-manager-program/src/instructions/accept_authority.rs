@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::error::DappTokenManagerError;
+use crate::events::DappTokenManagerAuthorityAccepted;
+use crate::state::DappTokenManager;
+
+// NOTE Step 2 of 2 for rotating the DTM authority.
+// The pending authority signs to prove it controls the key,
+// so the DTM can't be handed to a typo'd/unusable address.
+pub fn handler(ctx: Context<AcceptAuthority>) -> Result<()> {
+    let dapp_token_manager = &mut ctx.accounts.dapp_token_manager;
+    msg!(
+        "Rotating dapp_token_manager authority: {} -> {}",
+        dapp_token_manager.authority,
+        dapp_token_manager.pending_authority
+    );
+    let previous_authority = dapp_token_manager.authority;
+    dapp_token_manager.authority = dapp_token_manager.pending_authority;
+    dapp_token_manager.pending_authority = Pubkey::default();
+
+    emit!(DappTokenManagerAuthorityAccepted {
+        dapp_token_manager: dapp_token_manager.key(),
+        previous_authority,
+        new_authority: dapp_token_manager.authority,
+    });
+
+    Ok(())
+}
+
+// ------- Accounts Validation Struct ------
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        constraint = dapp_token_manager.pending_authority == pending_authority.key()
+            @ DappTokenManagerError::NotPendingAuthority,
+        seeds = [
+            DappTokenManager::SEED_PREFIX.as_ref(),
+            dapp_token_manager.mint.as_ref(),
+        ],
+        bump = dapp_token_manager.bump,
+    )]
+    pub dapp_token_manager: Account<'info, DappTokenManager>,
+
+    pub pending_authority: Signer<'info>,
+}
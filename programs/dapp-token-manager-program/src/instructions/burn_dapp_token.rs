@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+
+use crate::error::DappTokenManagerError;
+use crate::events::DappTokenBurned;
+use crate::state::DappTokenManager;
+
+// NOTE Token sink. The holder (user) is the authority of their own
+// token account, so the user signs the Burn, NOT the DTM PDA.
+// The DTM just records the totals on-chain.
+pub fn handler(ctx: Context<BurnDappToken>, amount: u64) -> Result<()> {
+    require!(amount > 0, DappTokenManagerError::ZeroAmount);
+
+    msg!("1. Burning supply from user token account (signed by user)...");
+    token_interface::burn(
+        CpiContext::new(
+            // Program involved
+            ctx.accounts.token_program.to_account_info(),
+            // IX accounts
+            token::Burn {
+                mint: ctx.accounts.mint.to_account_info(),
+                from: ctx.accounts.user_token_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    // Update DTM state
+    let dapp_token_manager = &mut ctx.accounts.dapp_token_manager;
+    dapp_token_manager.total_burned = dapp_token_manager
+        .total_burned
+        .checked_add(amount)
+        .ok_or(DappTokenManagerError::MathOverflow)?;
+    dapp_token_manager.circulating_supply = dapp_token_manager
+        .circulating_supply
+        .checked_sub(amount)
+        .ok_or(DappTokenManagerError::MathOverflow)?;
+
+    emit!(DappTokenBurned {
+        dapp_token_manager: dapp_token_manager.key(),
+        mint: ctx.accounts.mint.key(),
+        user: ctx.accounts.user.key(),
+        amount,
+        total_burned: dapp_token_manager.total_burned,
+        circulating_supply: dapp_token_manager.circulating_supply,
+    });
+
+    Ok(())
+}
+
+// ------- Accounts Validation Struct ------
+#[derive(Accounts)]
+pub struct BurnDappToken<'info> {
+    // U: Address check replaces associated_token::* (legacy-only derivation)
+    /// CHECK: User's ATA for (mint, token_program). Token program checks owner/balance
+    #[account(
+        mut,
+        address = token_interface::get_associated_token_address(
+            &user.key(),
+            &mint.key(),
+            &token_program.key(),
+        ) @ DappTokenManagerError::InvalidTokenAccount,
+    )]
+    pub user_token_account: UncheckedAccount<'info>,
+
+    // IMPORTANT: mint account MUST be mutable (supply decreases)
+    /// CHECK: Legacy Token or Token-2022 mint. Must be the DTM's mint
+    #[account(
+        mut,
+        constraint = mint.key() == dapp_token_manager.mint @ DappTokenManagerError::MintMismatch
+    )]
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            DappTokenManager::SEED_PREFIX.as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump = dapp_token_manager.bump,
+    )]
+    pub dapp_token_manager: Account<'info, DappTokenManager>,
+
+    pub user: Signer<'info>,
+
+    /// CHECK: Must be the token program recorded on the DTM (legacy or 2022)
+    #[account(
+        address = dapp_token_manager.token_program @ DappTokenManagerError::TokenProgramMismatch
+    )]
+    pub token_program: UncheckedAccount<'info>,
+}
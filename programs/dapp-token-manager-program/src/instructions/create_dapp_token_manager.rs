@@ -1,7 +1,10 @@
 use anchor_lang::{prelude::*, system_program};
-use anchor_spl::token::{self, Token};
+use anchor_spl::metadata::{self, Metadata};
+use anchor_spl::token;
 
-use crate::state::DappTokenManager;
+use crate::error::DappTokenManagerError;
+use crate::events::{DappTokenManagerCreated, DappTokenMetadataUpdated};
+use crate::state::{CreateDappTokenManagerArgs, DappTokenManager, TokenMetadataArgs};
 
 // Q: What about allowing a Caller Program to create
 // MULTIPLE DappTokenManagers? Or, what if DTM
@@ -11,12 +14,42 @@ use crate::state::DappTokenManager;
 // -------- Instruction Function --------
 pub fn handler(
     ctx: Context<CreateDappTokenManager>,
-    authority: Pubkey, // Keypair or PDA (flexible I think...)
-    supply_amount_per_mint: u64,
+    args: CreateDappTokenManagerArgs,
 ) -> Result<()> {
+    require!(args.supply_amount_per_mint > 0, DappTokenManagerError::ZeroAmount);
+    // NOTE The cooldown is added to last_mint_timestamp on every mint
+    require!(args.cooldown_seconds >= 0, DappTokenManagerError::InvalidCooldown);
+
+    // NOTE token_program is either legacy Token or Token-2022 (checked
+    // in the accounts struct). Extensions only exist on Token-2022, and
+    // Token Metadata (pinned ~1.8.3) only supports legacy Token mints.
+    let token_program_id = ctx.accounts.token_program.key();
+    let token_extensions = token_interface::MintExtensions::from(
+        &args.token_extensions.clone().unwrap_or_default(),
+    );
+    if !token_extensions.extension_types().is_empty() {
+        require_keys_eq!(
+            token_program_id,
+            spl_token_2022::ID,
+            DappTokenManagerError::ExtensionsRequireToken2022
+        );
+    }
+    if args.token_metadata.is_some() {
+        require_keys_eq!(
+            token_program_id,
+            token::ID,
+            DappTokenManagerError::MetadataRequiresLegacyToken
+        );
+    }
+
     // 1. CLI/Client: Create a Keypair
     // 2. Program: Create a System Account using Keypair.publicKey
     msg!("1. Creating system account to store the actual mint (token)...");
+    // NOTE Fund exactly the rent-exempt minimum for a Mint account
+    // instead of a hardcoded lamports amount that may over/under-fund.
+    // U: Size now includes any Token-2022 extensions (82 bytes if none)
+    let mint_space = token_interface::mint_space(&token_extensions);
+    let mint_lamports = ctx.accounts.rent.minimum_balance(mint_space);
     system_program::create_account(
         CpiContext::new(
             // Program involved
@@ -27,22 +60,22 @@ pub fn handler(
                 to: ctx.accounts.mint.to_account_info(),
             },
         ),
-        10000000,                          // lamports
-        82,                                // size
-        &ctx.accounts.token_program.key(), // owner - TOken Program
+        mint_lamports,                     // lamports (rent-exempt)
+        mint_space as u64,                 // size
+        &token_program_id,                 // owner - Token Program (legacy or 2022)
     )?;
 
     // 3. Program: Create the DTM PDA
-    msg!("2. Create DappTokenManager PDA using calling program.key() & mint.key() as seeds...");
+    msg!("2. Create DappTokenManager PDA using mint.key() as seed...");
     let dapp_token_manager = DappTokenManager::new(
-        authority, // caller program decides (Keypair, PDA)
+        &args, // args.authority: caller program decides (Keypair, PDA)
         ctx.accounts.mint.key(),
-        supply_amount_per_mint,
+        token_program_id,
         ctx.accounts.authority_payer.key(),
         // NOTE bumps.get("account_name"), NOT seed!
         *ctx.bumps
             .get("dapp_token_manager")
-            .expect("Bump not found."),
+            .ok_or(DappTokenManagerError::BumpNotFound)?,
     );
     msg!("DappTokenManager: {:?}", &dapp_token_manager);
 
@@ -52,26 +85,38 @@ pub fn handler(
         .set_inner(dapp_token_manager.clone());
 
     // 4. Program: Initialize the Mint - SPL Token Program
+    //      - 4.0 - (Token-2022) Initialize extensions FIRST
     //      - 4.1 - Set mint authority to DTM PDA
     //      - 4.2 - Set freeze authority to DTM PDA
+    // U: Using InitializeMint2 (no rent sysvar) through token_interface
+    // so the same CPI works for both token programs.
+    if !token_extensions.extension_types().is_empty() {
+        msg!("3a. Initializing Token-2022 mint extensions: {:?}", &token_extensions);
+        token_interface::initialize_mint_extensions(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::InitializeMint2 {
+                    mint: ctx.accounts.mint.to_account_info(),
+                },
+            ),
+            &token_extensions,
+            &ctx.accounts.dapp_token_manager.key(), // extension authority
+        )?;
+    }
+
     msg!("3. Initializing mint account and set authority to dapp_token_manager...");
-    token::initialize_mint(
+    token_interface::initialize_mint2(
         CpiContext::new_with_signer(
             // Program involved
             ctx.accounts.token_program.to_account_info(),
             // IX accounts
-            token::InitializeMint {
+            token::InitializeMint2 {
                 mint: ctx.accounts.mint.to_account_info(),
-                rent: ctx.accounts.rent.to_account_info(),
             },
             // Signer Seeds
-            &[&[
-                DappTokenManager::SEED_PREFIX.as_bytes(),
-                ctx.accounts.mint.key().as_ref(),
-                authority.as_ref(),
-            ]],
+            &[&ctx.accounts.dapp_token_manager.seeds()],
         ),
-        9,
+        args.decimals,
         &ctx.accounts.dapp_token_manager.key(), // mint authority
         Some(&ctx.accounts.dapp_token_manager.key()), // freeze authority
     )?;
@@ -80,12 +125,87 @@ pub fn handler(
         &ctx.accounts.mint.to_account_info()
     );
 
+    // 5. Program: (Optional) Create Metaplex metadata for the Mint
+    //      - DTM PDA signs as the mint authority
+    //      - DTM PDA is set as the update authority
+    if let Some(token_metadata) = &args.token_metadata {
+        // NOTE metadata/token_metadata_program are optional accounts,
+        // so the seeds check that used to be on 'metadata' is done here
+        let (metadata_account, token_metadata_program) = match (
+            &ctx.accounts.metadata,
+            &ctx.accounts.token_metadata_program,
+        ) {
+            (Some(metadata_account), Some(token_metadata_program)) => {
+                (metadata_account, token_metadata_program)
+            }
+            _ => return err!(DappTokenManagerError::MetadataAccountsRequired),
+        };
+        let (metadata_pda, _) = Pubkey::find_program_address(
+            &[
+                TokenMetadataArgs::SEED_PREFIX.as_ref(),
+                token_metadata_program.key().as_ref(),
+                ctx.accounts.mint.key().as_ref(),
+            ],
+            &token_metadata_program.key(),
+        );
+        require_keys_eq!(
+            metadata_account.key(),
+            metadata_pda,
+            DappTokenManagerError::InvalidMetadataAccount
+        );
+
+        msg!("4. Creating token metadata account (signing via dapp_token_manager PDA)...");
+        metadata::create_metadata_accounts_v3(
+            CpiContext::new_with_signer(
+                // Program involved
+                token_metadata_program.to_account_info(),
+                // IX accounts
+                metadata::CreateMetadataAccountsV3 {
+                    metadata: metadata_account.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    mint_authority: ctx.accounts.dapp_token_manager.to_account_info(),
+                    payer: ctx.accounts.authority_payer.to_account_info(),
+                    update_authority: ctx.accounts.dapp_token_manager.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    rent: ctx.accounts.rent.to_account_info(),
+                },
+                // Signer Seeds
+                &[&ctx.accounts.dapp_token_manager.seeds()],
+            ),
+            token_metadata.to_data_v2(),
+            true, // is_mutable (see update_dapp_token_metadata)
+            true, // update_authority_is_signer
+            None, // collection details
+        )?;
+
+        emit!(DappTokenMetadataUpdated {
+            dapp_token_manager: ctx.accounts.dapp_token_manager.key(),
+            mint: ctx.accounts.mint.key(),
+            metadata: metadata_account.key(),
+            name: token_metadata.name.clone(),
+            symbol: token_metadata.symbol.clone(),
+            uri: token_metadata.uri.clone(),
+        });
+    }
+
+    emit!(DappTokenManagerCreated {
+        dapp_token_manager: ctx.accounts.dapp_token_manager.key(),
+        mint: ctx.accounts.mint.key(),
+        token_program: token_program_id,
+        authority: args.authority,
+        authority_payer: ctx.accounts.authority_payer.key(),
+        decimals: args.decimals,
+        supply_amount_per_mint: args.supply_amount_per_mint,
+        max_supply: args.max_supply,
+        cooldown_seconds: args.cooldown_seconds,
+        max_mints_per_user: args.max_mints_per_user,
+    });
+
     Ok(())
 }
 
 // -------- Accounts Validation Struct -------
 #[derive(Accounts)]
-#[instruction(authority: Pubkey, supply_amount_per_mint: u64)]
 pub struct CreateDappTokenManager<'info> {
     // Client: Pass a Keypair
     #[account(mut)]
@@ -110,7 +230,6 @@ pub struct CreateDappTokenManager<'info> {
         seeds = [
             DappTokenManager::SEED_PREFIX.as_ref(),
             mint.key().as_ref(),
-            authority.as_ref(),
         ],
         bump
     )]
@@ -128,7 +247,21 @@ pub struct CreateDappTokenManager<'info> {
     #[account(mut)]
     pub authority_payer: Signer<'info>, // The fee payer (calling program wallet)
 
+    // NOTE Only needed (and then required) when 'token_metadata' is Some
+    // U: Optional account, so the PDA is checked in the handler instead
+    // of with seeds (seeds::program can't use the optional program)
+    /// CHECK: Metaplex metadata PDA. Validated in handler and created in CPI
+    #[account(mut)]
+    pub metadata: Option<UncheckedAccount<'info>>,
+
     pub rent: Sysvar<'info, Rent>,
-    pub token_program: Program<'info, Token>,
+    // U: Either Token program. The DTM records which one owns the mint.
+    /// CHECK: Must be the legacy Token program or Token-2022
+    #[account(
+        constraint = token_interface::is_token_program(token_program.key)
+            @ DappTokenManagerError::UnsupportedTokenProgram
+    )]
+    pub token_program: UncheckedAccount<'info>,
+    pub token_metadata_program: Option<Program<'info, Metadata>>,
     pub system_program: Program<'info, System>,
 }
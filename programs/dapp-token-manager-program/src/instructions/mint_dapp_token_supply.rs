@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token;
-use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::token;
 
-use crate::state::DappTokenManager;
+use crate::error::DappTokenManagerError;
+use crate::events::DappTokenSupplyMinted;
+use crate::state::{DappTokenManager, UserMintRecord};
 
 // Q: Where does Caller 'authority' fit into the equation? 
 // Perhaps the Caller has 'authority' over the DTM, 
@@ -22,17 +24,72 @@ use crate::state::DappTokenManager;
 // Otherwise, I don't really see a real use case.
 
 pub fn handler(ctx: Context<MintDappTokenSupply>) -> Result<()> {
+    let clock = Clock::get()?;
+    let dapp_token_manager = &ctx.accounts.dapp_token_manager;
+    let user_mint_record = &mut ctx.accounts.user_mint_record;
+
+    require!(!dapp_token_manager.paused, DappTokenManagerError::Paused);
+
+    // 0a. Program: Enforce per-user limits before minting anything
+    //    NOTE: user_mint_record is init_if_needed, so a brand new
+    //    record has total_mint_count == 0 and skips the cooldown check.
+    if user_mint_record.total_mint_count > 0 {
+        let next_mint_timestamp = user_mint_record
+            .last_mint_timestamp
+            .checked_add(dapp_token_manager.cooldown_seconds)
+            .ok_or(DappTokenManagerError::MathOverflow)?;
+        require!(
+            clock.unix_timestamp >= next_mint_timestamp,
+            DappTokenManagerError::MintCooldownNotElapsed
+        );
+    }
+    if dapp_token_manager.max_mints_per_user > 0 {
+        require!(
+            user_mint_record.total_mint_count < dapp_token_manager.max_mints_per_user,
+            DappTokenManagerError::UserMintLimitReached
+        );
+    }
+
+    // 0b. Program: Enforce the DTM's global supply cap (if any)
+    if dapp_token_manager.max_supply > 0 {
+        let mint = token_interface::unpack_mint(&ctx.accounts.mint)?;
+        let new_supply = mint
+            .supply
+            .checked_add(dapp_token_manager.supply_amount_per_mint)
+            .ok_or(DappTokenManagerError::SupplyCapExceeded)?;
+        require!(
+            new_supply <= dapp_token_manager.max_supply,
+            DappTokenManagerError::SupplyCapExceeded
+        );
+    }
+
     // 1. Program: Create ATA for the user wallet
     //    NOTE: Will need 'init_if_needed' feature
     // Q: Do I need to do anything in this handler or is all taken care of
     // because of init_if_needed?
     // A: Nope! init_if_needed handles it all!
+    // U: Not anymore. init_if_needed + associated_token::* derive the ATA
+    // with the legacy Token program id only, so create it (idempotent)
+    // here against whichever token program owns the mint.
+    token_interface::create_associated_token_account_idempotent(CpiContext::new(
+        // Program involved
+        ctx.accounts.associated_token_program.to_account_info(),
+        // IX accounts
+        associated_token::Create {
+            payer: ctx.accounts.user.to_account_info(),
+            associated_token: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+        },
+    ))?;
 
     // 2. Program: Use MintTo IX to mint new supply to ATA
     //    NOTE: 'mint' account MUST be mutable
     //    NOTE: Signed/authorized by DTM PDA (CpiContext::new_with_signer())
     msg!("1. Minting supply to user token account (signing via dapp_token_manager PDA)...");
-    token::mint_to(
+    token_interface::mint_to(
         CpiContext::new_with_signer(
             // Program involved
             ctx.accounts.token_program.to_account_info(),
@@ -43,22 +100,50 @@ pub fn handler(ctx: Context<MintDappTokenSupply>) -> Result<()> {
                 authority: ctx.accounts.dapp_token_manager.to_account_info(),
             },
             // Signer Seeds
-            &[&[
-                DappTokenManager::SEED_PREFIX.as_bytes(),
-                ctx.accounts.mint.key().as_ref(),
-                ctx.accounts
-                    .dapp_token_manager
-                    .authority
-                    .key()
-                    .as_ref(),
-                &[ctx.accounts.dapp_token_manager.bump]
-            ]],
+            &[&ctx.accounts.dapp_token_manager.seeds()],
         ),
         ctx.accounts.dapp_token_manager.supply_amount_per_mint, // amount to mint each time
     )?;
 
     // Update DTM state
-    ctx.accounts.dapp_token_manager.total_mint_count += 1;
+    let dapp_token_manager = &mut ctx.accounts.dapp_token_manager;
+    dapp_token_manager.total_mint_count = dapp_token_manager
+        .total_mint_count
+        .checked_add(1)
+        .ok_or(DappTokenManagerError::MathOverflow)?;
+    dapp_token_manager.circulating_supply = dapp_token_manager
+        .circulating_supply
+        .checked_add(dapp_token_manager.supply_amount_per_mint)
+        .ok_or(DappTokenManagerError::MathOverflow)?;
+
+    // Update the user's mint record
+    let user_mint_record = &mut ctx.accounts.user_mint_record;
+    if user_mint_record.total_mint_count == 0 {
+        user_mint_record.dapp_token_manager = ctx.accounts.dapp_token_manager.key();
+        user_mint_record.user = ctx.accounts.user.key();
+        user_mint_record.bump = *ctx
+            .bumps
+            .get("user_mint_record")
+            .ok_or(DappTokenManagerError::BumpNotFound)?;
+    }
+    user_mint_record.total_mint_count = user_mint_record
+        .total_mint_count
+        .checked_add(1)
+        .ok_or(DappTokenManagerError::MathOverflow)?;
+    user_mint_record.total_minted_amount = user_mint_record
+        .total_minted_amount
+        .checked_add(ctx.accounts.dapp_token_manager.supply_amount_per_mint)
+        .ok_or(DappTokenManagerError::MathOverflow)?;
+    user_mint_record.last_mint_slot = clock.slot;
+    user_mint_record.last_mint_timestamp = clock.unix_timestamp;
+
+    emit!(DappTokenSupplyMinted {
+        dapp_token_manager: ctx.accounts.dapp_token_manager.key(),
+        mint: ctx.accounts.mint.key(),
+        user: ctx.accounts.user.key(),
+        amount: ctx.accounts.dapp_token_manager.supply_amount_per_mint,
+        total_mint_count: ctx.accounts.dapp_token_manager.total_mint_count,
+    });
 
     Ok(())
 }
@@ -66,20 +151,26 @@ pub fn handler(ctx: Context<MintDappTokenSupply>) -> Result<()> {
 // ------- Accounts Validation Struct ------
 #[derive(Accounts)]
 pub struct MintDappTokenSupply<'info> {
+    // U: UncheckedAccount (was init_if_needed Account<TokenAccount>) so it
+    // can be a Token-2022 ATA. Created if needed in the handler.
+    /// CHECK: User's ATA for (mint, token_program). Address validated here
     #[account(
-        init_if_needed,
-        payer = user,
-        associated_token::mint = mint,
-        associated_token::authority = user,
+        mut,
+        address = token_interface::get_associated_token_address(
+            &user.key(),
+            &mint.key(),
+            &token_program.key(),
+        ) @ DappTokenManagerError::InvalidTokenAccount,
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: UncheckedAccount<'info>,
 
     // IMPORTANT: mint account MUST be mutable
+    /// CHECK: Legacy Token or Token-2022 mint. Must be the DTM's mint
     #[account(
         mut,
-        constraint = mint.key() == dapp_token_manager.mint
+        constraint = mint.key() == dapp_token_manager.mint @ DappTokenManagerError::MintMismatch
     )]
-    pub mint: Account<'info, Mint>,
+    pub mint: UncheckedAccount<'info>,
 
     #[account(
         mut,
@@ -95,16 +186,30 @@ pub struct MintDappTokenSupply<'info> {
         // U: Removing has_one = authority. Just don't think I need it,
         // since this is a MintTo, which just needs DTM to sign
         // has_one = authority,
-        constraint = dapp_token_manager.mint == mint.key(),
+        constraint = dapp_token_manager.mint == mint.key() @ DappTokenManagerError::MintMismatch,
         seeds = [
             DappTokenManager::SEED_PREFIX.as_ref(),
             mint.key().as_ref(),
-            dapp_token_manager.authority.as_ref(),
         ],
         bump = dapp_token_manager.bump,
     )]
     pub dapp_token_manager: Account<'info, DappTokenManager>,
 
+    // NOTE Tracks how often/how much this user has minted
+    // from this DTM. User pays for it on their first mint.
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserMintRecord::ACCOUNT_SPACE,
+        seeds = [
+            UserMintRecord::SEED_PREFIX.as_ref(),
+            dapp_token_manager.key().as_ref(),
+            user.key().as_ref(),
+        ],
+        bump
+    )]
+    pub user_mint_record: Account<'info, UserMintRecord>,
+
     // NOTE This is whatever the Caller Program passed as 
     // IX data to the create_dapp_token_manager() method.
     // Could be PDA or Keypair.
@@ -122,6 +227,10 @@ pub struct MintDappTokenSupply<'info> {
 
     pub rent: Sysvar<'info, Rent>,
     pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
-    pub token_program: Program<'info, Token>,
+    /// CHECK: Must be the token program recorded on the DTM (legacy or 2022)
+    #[account(
+        address = dapp_token_manager.token_program @ DappTokenManagerError::TokenProgramMismatch
+    )]
+    pub token_program: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
 }
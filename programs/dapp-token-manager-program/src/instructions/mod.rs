@@ -1,7 +1,19 @@
+pub mod accept_authority;
+pub mod burn_dapp_token;
 pub mod create_dapp_token_manager;
+pub mod freeze_user_account;
 pub mod mint_dapp_token_supply;
+pub mod propose_authority;
+pub mod thaw_user_account;
+pub mod update_dapp_token_manager;
+pub mod update_dapp_token_metadata;
 
+pub use accept_authority::*;
+pub use burn_dapp_token::*;
 pub use create_dapp_token_manager::*;
+pub use freeze_user_account::*;
 pub use mint_dapp_token_supply::*;
-
-
+pub use propose_authority::*;
+pub use thaw_user_account::*;
+pub use update_dapp_token_manager::*;
+pub use update_dapp_token_metadata::*;
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::error::DappTokenManagerError;
+use crate::events::DappTokenManagerAuthorityProposed;
+use crate::state::DappTokenManager;
+
+// NOTE Step 1 of 2 for rotating the DTM authority.
+// The current authority proposes a new authority, which must
+// then sign accept_authority() to complete the handoff.
+// Proposing Pubkey::default() cancels a pending handoff.
+pub fn handler(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+    msg!("Proposing new dapp_token_manager authority: {}", new_authority);
+    ctx.accounts.dapp_token_manager.pending_authority = new_authority;
+
+    emit!(DappTokenManagerAuthorityProposed {
+        dapp_token_manager: ctx.accounts.dapp_token_manager.key(),
+        authority: ctx.accounts.authority.key(),
+        pending_authority: new_authority,
+    });
+
+    Ok(())
+}
+
+// ------- Accounts Validation Struct ------
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(
+        mut,
+        has_one = authority @ DappTokenManagerError::Unauthorized,
+        seeds = [
+            DappTokenManager::SEED_PREFIX.as_ref(),
+            dapp_token_manager.mint.as_ref(),
+        ],
+        bump = dapp_token_manager.bump,
+    )]
+    pub dapp_token_manager: Account<'info, DappTokenManager>,
+
+    // NOTE Current authority (Keypair or Caller Program PDA via CPI)
+    pub authority: Signer<'info>,
+}
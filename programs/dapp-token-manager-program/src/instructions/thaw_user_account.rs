@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+
+use crate::error::DappTokenManagerError;
+use crate::events::DappTokenAccountThawed;
+use crate::state::DappTokenManager;
+
+// NOTE Reverses freeze_user_account. Same authority gating and
+// the DTM PDA signs the ThawAccount CPI as the freeze authority.
+pub fn handler(ctx: Context<ThawUserAccount>) -> Result<()> {
+    msg!(
+        "Thawing user token account {} (signing via dapp_token_manager PDA)...",
+        ctx.accounts.user_token_account.key()
+    );
+    // NOTE Unpacked here since it's an UncheckedAccount (legacy or 2022)
+    let user_token_account = token_interface::unpack_token_account(&ctx.accounts.user_token_account)?;
+    require_keys_eq!(
+        user_token_account.mint,
+        ctx.accounts.mint.key(),
+        DappTokenManagerError::MintMismatch
+    );
+
+    token_interface::thaw_account(CpiContext::new_with_signer(
+        // Program involved
+        ctx.accounts.token_program.to_account_info(),
+        // IX accounts
+        token::ThawAccount {
+            account: ctx.accounts.user_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            authority: ctx.accounts.dapp_token_manager.to_account_info(),
+        },
+        // Signer Seeds
+        &[&ctx.accounts.dapp_token_manager.seeds()],
+    ))?;
+
+    emit!(DappTokenAccountThawed {
+        dapp_token_manager: ctx.accounts.dapp_token_manager.key(),
+        mint: ctx.accounts.mint.key(),
+        user_token_account: ctx.accounts.user_token_account.key(),
+        owner: user_token_account.owner,
+    });
+
+    Ok(())
+}
+
+// ------- Accounts Validation Struct ------
+#[derive(Accounts)]
+pub struct ThawUserAccount<'info> {
+    // U: Mint is checked in the handler after unpacking (legacy or 2022)
+    /// CHECK: Any token account of the DTM's mint
+    #[account(mut)]
+    pub user_token_account: UncheckedAccount<'info>,
+
+    /// CHECK: Legacy Token or Token-2022 mint. Must be the DTM's mint
+    #[account(
+        constraint = mint.key() == dapp_token_manager.mint @ DappTokenManagerError::MintMismatch
+    )]
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(
+        has_one = authority @ DappTokenManagerError::Unauthorized,
+        seeds = [
+            DappTokenManager::SEED_PREFIX.as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump = dapp_token_manager.bump,
+    )]
+    pub dapp_token_manager: Account<'info, DappTokenManager>,
+
+    pub authority: Signer<'info>,
+
+    /// CHECK: Must be the token program recorded on the DTM (legacy or 2022)
+    #[account(
+        address = dapp_token_manager.token_program @ DappTokenManagerError::TokenProgramMismatch
+    )]
+    pub token_program: UncheckedAccount<'info>,
+}
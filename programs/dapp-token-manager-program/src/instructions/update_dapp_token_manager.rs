@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::error::DappTokenManagerError;
+use crate::events::DappTokenManagerUpdated;
+use crate::state::DappTokenManager;
+
+// NOTE Admin-only. Pass None to leave a setting unchanged.
+// E.g., update_dapp_token_manager(None, Some(true)) just pauses minting.
+pub fn handler(
+    ctx: Context<UpdateDappTokenManager>,
+    supply_amount_per_mint: Option<u64>,
+    paused: Option<bool>,
+) -> Result<()> {
+    let dapp_token_manager = &mut ctx.accounts.dapp_token_manager;
+
+    if let Some(supply_amount_per_mint) = supply_amount_per_mint {
+        require!(supply_amount_per_mint > 0, DappTokenManagerError::ZeroAmount);
+        msg!("Updating supply_amount_per_mint to {}", supply_amount_per_mint);
+        dapp_token_manager.supply_amount_per_mint = supply_amount_per_mint;
+    }
+
+    if let Some(paused) = paused {
+        msg!("Updating paused to {}", paused);
+        dapp_token_manager.paused = paused;
+    }
+
+    emit!(DappTokenManagerUpdated {
+        dapp_token_manager: dapp_token_manager.key(),
+        authority: dapp_token_manager.authority,
+        supply_amount_per_mint: dapp_token_manager.supply_amount_per_mint,
+        paused: dapp_token_manager.paused,
+    });
+
+    Ok(())
+}
+
+// ------- Accounts Validation Struct ------
+#[derive(Accounts)]
+pub struct UpdateDappTokenManager<'info> {
+    #[account(
+        mut,
+        has_one = authority @ DappTokenManagerError::Unauthorized,
+        seeds = [
+            DappTokenManager::SEED_PREFIX.as_ref(),
+            dapp_token_manager.mint.as_ref(),
+        ],
+        bump = dapp_token_manager.bump,
+    )]
+    pub dapp_token_manager: Account<'info, DappTokenManager>,
+
+    pub authority: Signer<'info>,
+}
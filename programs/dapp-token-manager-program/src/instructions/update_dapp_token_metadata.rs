@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::{self, Metadata};
+
+use crate::error::DappTokenManagerError;
+use crate::events::DappTokenMetadataUpdated;
+use crate::state::{DappTokenManager, TokenMetadataArgs};
+
+// NOTE Admin-only. The DTM PDA is the metadata update authority,
+// so the DTM signs the UpdateMetadataAccountsV2 CPI on behalf
+// of the DTM authority.
+pub fn handler(ctx: Context<UpdateDappTokenMetadata>, token_metadata: TokenMetadataArgs) -> Result<()> {
+    msg!("Updating token metadata (signing via dapp_token_manager PDA)...");
+    metadata::update_metadata_accounts_v2(
+        CpiContext::new_with_signer(
+            // Program involved
+            ctx.accounts.token_metadata_program.to_account_info(),
+            // IX accounts
+            metadata::UpdateMetadataAccountsV2 {
+                metadata: ctx.accounts.metadata.to_account_info(),
+                update_authority: ctx.accounts.dapp_token_manager.to_account_info(),
+            },
+            // Signer Seeds
+            &[&ctx.accounts.dapp_token_manager.seeds()],
+        ),
+        None, // new_update_authority (keep DTM PDA)
+        Some(token_metadata.to_data_v2()),
+        None, // primary_sale_happened
+        None, // is_mutable
+    )?;
+
+    emit!(DappTokenMetadataUpdated {
+        dapp_token_manager: ctx.accounts.dapp_token_manager.key(),
+        mint: ctx.accounts.dapp_token_manager.mint,
+        metadata: ctx.accounts.metadata.key(),
+        name: token_metadata.name,
+        symbol: token_metadata.symbol,
+        uri: token_metadata.uri,
+    });
+
+    Ok(())
+}
+
+// ------- Accounts Validation Struct ------
+#[derive(Accounts)]
+pub struct UpdateDappTokenMetadata<'info> {
+    #[account(
+        has_one = authority @ DappTokenManagerError::Unauthorized,
+        seeds = [
+            DappTokenManager::SEED_PREFIX.as_ref(),
+            dapp_token_manager.mint.as_ref(),
+        ],
+        bump = dapp_token_manager.bump,
+    )]
+    pub dapp_token_manager: Account<'info, DappTokenManager>,
+
+    /// CHECK: Metaplex metadata PDA. Validated by seeds and in CPI
+    #[account(
+        mut,
+        seeds = [
+            TokenMetadataArgs::SEED_PREFIX.as_ref(),
+            token_metadata_program.key().as_ref(),
+            dapp_token_manager.mint.as_ref(),
+        ],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub metadata: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+
+    pub token_metadata_program: Program<'info, Metadata>,
+}
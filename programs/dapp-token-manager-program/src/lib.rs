@@ -3,6 +3,8 @@ use anchor_lang::prelude::*;
 use instructions::*;
 use state::*;
 
+pub mod error;
+pub mod events;
 pub mod instructions;
 pub mod state;
 
@@ -30,14 +32,48 @@ pub mod dapp_token_manager_program {
 
     pub fn create_dapp_token_manager(
         ctx: Context<CreateDappTokenManager>, 
-        authority: Pubkey,
-        supply_amount_per_mint: u64,
+        args: CreateDappTokenManagerArgs,
     ) -> Result<()> {
-        instructions::create_dapp_token_manager::handler(ctx, authority, supply_amount_per_mint)
+        instructions::create_dapp_token_manager::handler(ctx, args)
     }
 
     pub fn mint_dapp_token_supply(ctx: Context<MintDappTokenSupply>) -> Result<()> {
         instructions::mint_dapp_token_supply::handler(ctx)
     }
 
+    pub fn burn_dapp_token(ctx: Context<BurnDappToken>, amount: u64) -> Result<()> {
+        instructions::burn_dapp_token::handler(ctx, amount)
+    }
+
+    pub fn freeze_user_account(ctx: Context<FreezeUserAccount>) -> Result<()> {
+        instructions::freeze_user_account::handler(ctx)
+    }
+
+    pub fn thaw_user_account(ctx: Context<ThawUserAccount>) -> Result<()> {
+        instructions::thaw_user_account::handler(ctx)
+    }
+
+    pub fn update_dapp_token_metadata(
+        ctx: Context<UpdateDappTokenMetadata>,
+        token_metadata: TokenMetadataArgs,
+    ) -> Result<()> {
+        instructions::update_dapp_token_metadata::handler(ctx, token_metadata)
+    }
+
+    pub fn update_dapp_token_manager(
+        ctx: Context<UpdateDappTokenManager>,
+        supply_amount_per_mint: Option<u64>,
+        paused: Option<bool>,
+    ) -> Result<()> {
+        instructions::update_dapp_token_manager::handler(ctx, supply_amount_per_mint, paused)
+    }
+
+    pub fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+        instructions::propose_authority::handler(ctx, new_authority)
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        instructions::accept_authority::handler(ctx)
+    }
+
 }
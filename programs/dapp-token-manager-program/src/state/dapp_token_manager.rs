@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 
+use crate::state::{TokenExtensionsArgs, TokenMetadataArgs};
 
 #[account]
 #[derive(Default, Debug)]
@@ -17,10 +18,19 @@ pub struct DappTokenManager {
     // as Instruction Data to set 'authority' and pay however it wants
     // 8 bytes for Discrimator
     pub authority: Pubkey,          // 32 bytes Passed via IX data
+    pub pending_authority: Pubkey,  // 32 bytes Proposed new authority (default = none)
     pub mint: Pubkey,               // 32 bytes
+    pub token_program: Pubkey,      // 32 bytes Legacy Token or Token-2022 (owns the mint)
     pub supply_amount_per_mint: u64,// 8 bytes 
+    pub decimals: u8,               // 1 byte
+    pub max_supply: u64,            // 8 bytes Mint supply ceiling (0 = unlimited)
     pub authority_payer: Pubkey,    // 32 bytes Initializer/Payer
     pub total_mint_count: u64,      // 8 bytes
+    pub total_burned: u64,          // 8 bytes Raw amount burned via burn_dapp_token
+    pub circulating_supply: u64,    // 8 bytes Raw amount minted - burned
+    pub cooldown_seconds: i64,      // 8 bytes Per-user wait between mints
+    pub max_mints_per_user: u64,    // 8 bytes Per-user lifetime cap (0 = unlimited)
+    pub paused: bool,               // 1 byte
     pub bump: u8,                   // 1 byte
 }
 
@@ -28,32 +38,93 @@ pub struct DappTokenManager {
 // REF: https://lorisleiva.com/create-a-solana-dapp-from-scratch/structuring-our-tweet-account#final-code
 const DISCRIMINATOR_LENGTH: usize = 8;
 const AUTHORITY_LENGTH: usize = 32; // Pubkey
+const PENDING_AUTHORITY_LENGTH: usize = 32; // Pubkey
 const MINT_LENGTH: usize = 32; // Pubkey
+const TOKEN_PROGRAM_LENGTH: usize = 32; // Pubkey
 const SUPPLY_AMOUNT_PER_MINT_LENGTH: usize = 8; // u64
+const DECIMALS_LENGTH: usize = 1; // u8
+const MAX_SUPPLY_LENGTH: usize = 8; // u64
 const AUTHORITY_PAYER_LENGTH: usize = 32; // Pubkey
 const TOTAL_MINT_COUNT_LENGTH: usize = 8; // u64
+const TOTAL_BURNED_LENGTH: usize = 8; // u64
+const CIRCULATING_SUPPLY_LENGTH: usize = 8; // u64
+const COOLDOWN_SECONDS_LENGTH: usize = 8; // i64
+const MAX_MINTS_PER_USER_LENGTH: usize = 8; // u64
+const PAUSED_LENGTH: usize = 1; // bool
 const BUMP_LENGTH: usize = 1;
 
 
 impl DappTokenManager {
     pub const ACCOUNT_SPACE: usize = DISCRIMINATOR_LENGTH
         + AUTHORITY_LENGTH
+        + PENDING_AUTHORITY_LENGTH
         + MINT_LENGTH
+        + TOKEN_PROGRAM_LENGTH
         + SUPPLY_AMOUNT_PER_MINT_LENGTH
+        + DECIMALS_LENGTH
+        + MAX_SUPPLY_LENGTH
         + AUTHORITY_PAYER_LENGTH
         + TOTAL_MINT_COUNT_LENGTH
+        + TOTAL_BURNED_LENGTH
+        + CIRCULATING_SUPPLY_LENGTH
+        + COOLDOWN_SECONDS_LENGTH
+        + MAX_MINTS_PER_USER_LENGTH
+        + PAUSED_LENGTH
         + BUMP_LENGTH;
 
+    // NOTE Seeds are [SEED_PREFIX, mint]. The authority is NOT a seed,
+    // so it can be rotated (propose_authority/accept_authority)
+    // without changing the DTM address (i.e., the mint authority).
     pub const SEED_PREFIX: &'static str = "dapp-token-manager";
     // NOTE To get MAX of type: u32::MAX
-    pub fn new(authority: Pubkey, mint: Pubkey, supply_amount_per_mint: u64, authority_payer: Pubkey, bump: u8) -> Self {
+    pub fn new(
+        args: &CreateDappTokenManagerArgs,
+        mint: Pubkey,
+        token_program: Pubkey,
+        authority_payer: Pubkey,
+        bump: u8,
+    ) -> Self {
         DappTokenManager {
-            authority,
+            authority: args.authority,
+            pending_authority: Pubkey::default(),
             mint,
-            supply_amount_per_mint,
+            token_program,
+            supply_amount_per_mint: args.supply_amount_per_mint,
+            decimals: args.decimals,
+            max_supply: args.max_supply,
             authority_payer,
             total_mint_count: 0,
+            total_burned: 0,
+            circulating_supply: 0,
+            cooldown_seconds: args.cooldown_seconds,
+            max_mints_per_user: args.max_mints_per_user,
+            paused: false,
             bump,
         }
     }
+
+    // NOTE Use slice::from_ref() for the bump to avoid the
+    // 'temporary value created' error with &[self.bump]
+    // Usage: CpiContext::new_with_signer(.., &[&dapp_token_manager.seeds()])
+    pub fn seeds(&self) -> [&[u8]; 3] {
+        [
+            Self::SEED_PREFIX.as_bytes(),
+            self.mint.as_ref(),
+            std::slice::from_ref(&self.bump),
+        ]
+    }
+}
+
+// NOTE Instruction data for create_dapp_token_manager. Grouped into one
+// struct so new options don't keep growing the positional arg list.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct CreateDappTokenManagerArgs {
+    pub authority: Pubkey, // Keypair or PDA (caller program decides)
+    pub supply_amount_per_mint: u64,
+    pub decimals: u8,
+    pub max_supply: u64,         // 0 = unlimited
+    pub cooldown_seconds: i64,   // must be >= 0
+    pub max_mints_per_user: u64, // 0 = unlimited
+    pub token_metadata: Option<TokenMetadataArgs>,
+    pub token_extensions: Option<TokenExtensionsArgs>,
 }
@@ -1,3 +1,9 @@
 pub mod dapp_token_manager;
+pub mod token_extensions;
+pub mod token_metadata;
+pub mod user_mint_record;
 
 pub use dapp_token_manager::*;
+pub use token_extensions::*;
+pub use token_metadata::*;
+pub use user_mint_record::*;
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+// NOTE Instruction data (not an account) for opting a new mint into
+// Token-2022 extensions. Extensions can only be added when the mint is
+// created, so this is only read by create_dapp_token_manager.
+// The DTM PDA is set as every extension's authority (fee config,
+// withheld fees, rate), same as it is for mint/freeze.
+// U: The logic lives in token_interface::MintExtensions. This copy is
+// only here so the type shows up in the IDL (see From below).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct TokenExtensionsArgs {
+    pub transfer_fee: Option<TransferFeeArgs>,
+    pub non_transferable: bool,
+    pub interest_rate: Option<i16>, // basis points
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct TransferFeeArgs {
+    pub basis_points: u16,
+    pub maximum_fee: u64, // raw amount
+}
+
+// NOTE Destructures every field, so this stops compiling if either
+// side gains or loses one
+impl From<&TokenExtensionsArgs> for token_interface::MintExtensions {
+    fn from(args: &TokenExtensionsArgs) -> Self {
+        let TokenExtensionsArgs {
+            transfer_fee,
+            non_transferable,
+            interest_rate,
+        } = args;
+        Self {
+            transfer_fee: transfer_fee.as_ref().map(
+                |TransferFeeArgs { basis_points, maximum_fee }| token_interface::TransferFee {
+                    basis_points: *basis_points,
+                    maximum_fee: *maximum_fee,
+                },
+            ),
+            non_transferable: *non_transferable,
+            interest_rate: *interest_rate,
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use mpl_token_metadata::state::DataV2;
+
+// NOTE Instruction data (not an account) for the Metaplex metadata
+// account of the DTM's mint. Wallets/explorers read name, symbol and uri
+// from it instead of showing "Unknown token".
+// Max lengths are enforced by the Token Metadata Program (32/10/200).
+// U: The logic lives in token_interface::TokenMetadata. This copy is
+// only here so the type shows up in the IDL (see From below).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct TokenMetadataArgs {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
+
+impl TokenMetadataArgs {
+    pub const SEED_PREFIX: &'static str = token_interface::TokenMetadata::SEED_PREFIX;
+
+    pub fn to_data_v2(&self) -> DataV2 {
+        token_interface::TokenMetadata::from(self).to_data_v2()
+    }
+}
+
+// NOTE Destructures every field, so this stops compiling if either
+// side gains or loses one
+impl From<&TokenMetadataArgs> for token_interface::TokenMetadata {
+    fn from(args: &TokenMetadataArgs) -> Self {
+        let TokenMetadataArgs { name, symbol, uri } = args;
+        Self {
+            name: name.clone(),
+            symbol: symbol.clone(),
+            uri: uri.clone(),
+        }
+    }
+}
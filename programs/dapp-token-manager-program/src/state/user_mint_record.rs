@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+// NOTE One record per (dapp_token_manager, user) pair. Created
+// on the user's first mint via init_if_needed, so the user pays.
+// Used to enforce the DTM's cooldown_seconds and max_mints_per_user.
+#[account]
+#[derive(Default, Debug)]
+pub struct UserMintRecord {
+    // 8 bytes for Discrimator
+    pub dapp_token_manager: Pubkey, // 32 bytes
+    pub user: Pubkey,               // 32 bytes
+    pub total_mint_count: u64,      // 8 bytes
+    pub total_minted_amount: u64,   // 8 bytes
+    pub last_mint_slot: u64,        // 8 bytes
+    pub last_mint_timestamp: i64,   // 8 bytes
+    pub bump: u8,                   // 1 byte
+}
+
+const DISCRIMINATOR_LENGTH: usize = 8;
+const DAPP_TOKEN_MANAGER_LENGTH: usize = 32; // Pubkey
+const USER_LENGTH: usize = 32; // Pubkey
+const TOTAL_MINT_COUNT_LENGTH: usize = 8; // u64
+const TOTAL_MINTED_AMOUNT_LENGTH: usize = 8; // u64
+const LAST_MINT_SLOT_LENGTH: usize = 8; // u64
+const LAST_MINT_TIMESTAMP_LENGTH: usize = 8; // i64
+const BUMP_LENGTH: usize = 1;
+
+impl UserMintRecord {
+    pub const ACCOUNT_SPACE: usize = DISCRIMINATOR_LENGTH
+        + DAPP_TOKEN_MANAGER_LENGTH
+        + USER_LENGTH
+        + TOTAL_MINT_COUNT_LENGTH
+        + TOTAL_MINTED_AMOUNT_LENGTH
+        + LAST_MINT_SLOT_LENGTH
+        + LAST_MINT_TIMESTAMP_LENGTH
+        + BUMP_LENGTH;
+
+    pub const SEED_PREFIX: &'static str = "user-mint-record";
+}
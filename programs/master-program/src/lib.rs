@@ -1,15 +1,26 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::{get_return_data, invoke_signed, set_return_data},
+};
 use anchor_spl::associated_token;
-use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::token::Token;
 
 use dapp_token_manager_program::{
     self,
-    cpi::accounts::{CreateDappTokenManager, MintDappTokenSupply},
-    instructions::{create_dapp_token_manager, mint_dapp_token_supply},
+    cpi::accounts::{
+        AcceptAuthority, CreateDappTokenManager, FreezeUserAccount, MintDappTokenSupply,
+        ProposeAuthority, ThawUserAccount, UpdateDappTokenManager, UpdateDappTokenMetadata,
+    },
     program::DappTokenManagerProgram,
-    state::DappTokenManager,
+    state::{CreateDappTokenManagerArgs, DappTokenManager, TokenMetadataArgs},
+};
+use puppet_program::{
+    self,
+    cpi::accounts::{ClosePuppet, SetData, SetPayload, TransferPuppetAuthority},
+    program::PuppetProgram,
+    Puppet, SetDataResult,
 };
-use puppet_program::{self, cpi::accounts::SetData, program::PuppetProgram, Puppet};
 
 // ---------
 // IMPORTANT: CPI extends the privileges of caller (master) to callee (puppet).
@@ -67,41 +78,468 @@ declare_id!("CXdpazvEeifrgWfQGbwbtokAewZPsGSGJ2tRCe1Bif8g");
 #[program]
 mod master_program {
     use super::*;
+    // === Create the per-puppet authority PDA (stores canonical bump)
+    // NOTE Client flow:
+    // 1. Derive PuppetAuthority PDA: [SEED_PREFIX, puppet.key()] + masterProgram
+    // 2. puppetProgram.initialize(owner) (or any puppet 'owner' controls)
+    // 3. masterProgram.initialize_puppet_authority() (this IX)
+    // U: The puppet's current authority ('owner') signs, and the puppet is
+    // handed to our PDA in this same IX. Previously the puppet pointed at the
+    // PDA first, so anyone could front-run step 3 and record themselves as owner.
+    pub fn initialize_puppet_authority(ctx: Context<InitializePuppetAuthority>) -> Result<()> {
+        let puppet_authority = &mut ctx.accounts.puppet_authority;
+        puppet_authority.puppet = ctx.accounts.puppet.key();
+        puppet_authority.owner = ctx.accounts.owner.key();
+        puppet_authority.bump = *ctx
+            .bumps
+            .get("puppet_authority")
+            .ok_or(MasterError::BumpNotFound)?;
+
+        puppet_program::cpi::transfer_puppet_authority(
+            ctx.accounts.transfer_puppet_authority_ctx(),
+            ctx.accounts.puppet_authority.key(),
+        )
+    }
+
     // === CPI into Puppet Program
-    pub fn pull_strings(ctx: Context<PullStrings>, bump: u8, data: u64) -> Result<()> {
+    pub fn pull_strings<'info>(
+        ctx: Context<'_, '_, '_, 'info, PullStrings<'info>>,
+        data: u64,
+    ) -> Result<PullStringsResult> {
         // ==== Using a PDA instead of Keypair for puppet ====
-        let bump = &[bump][..];
+        // U: No longer trusting a caller-supplied bump. PullStrings validates
+        // 'authority' against [SEED_PREFIX, puppet] + the stored canonical bump,
+        // so only master_program can sign for its puppets.
         // Hit the set_data method on the puppet program
         // NOTE Pass our CPI Context to set_data() instruction.
         // The only difference is this expects a CpiContext instead of just Context.
         // U: With PDA, need to sign CpiContext with PDA seeds
-        puppet_program::cpi::set_data(
-            // NOTE Don't think we have any seeds besides the 'bump', so not passing as much
-            ctx.accounts.set_data_ctx().with_signer(&[&[bump][..]]),
+        // NOTE Forward remaining accounts so the puppet's PuppetHistory
+        // records this update (required once the puppet has one)
+        let result = puppet_program::cpi::set_data(
+            ctx.accounts
+                .set_data_ctx()
+                .with_signer(&[&ctx.accounts.authority.seeds()])
+                .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
             data,
+        )?;
+        // NOTE get() calls sol_get_return_data and deserializes the
+        // puppet's SetDataResult. Forward it as master's own return data.
+        let set_data_result = result.get();
+        msg!(
+            "Puppet data: {} -> {}",
+            set_data_result.previous_data,
+            set_data_result.new_data
+        );
+
+        Ok(set_data_result.into())
+    }
+
+    // === CPI into Puppet Program (compare-and-swap)
+    // NOTE Same accounts as pull_strings(). Fails with the puppet's
+    // DataMismatch error if another controller changed the data first.
+    pub fn compare_and_pull_strings<'info>(
+        ctx: Context<'_, '_, '_, 'info, PullStrings<'info>>,
+        expected: u64,
+        new: u64,
+    ) -> Result<PullStringsResult> {
+        let result = puppet_program::cpi::compare_and_set_data(
+            ctx.accounts
+                .set_data_ctx()
+                .with_signer(&[&ctx.accounts.authority.seeds()])
+                .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+            expected,
+            new,
+        )?;
+
+        Ok(result.get().into())
+    }
+
+    // === Batched CPI into Puppet Program
+    // NOTE remaining_accounts holds one (puppet, puppet_authority) pair per
+    // value in 'data', in the same order, each followed by the puppet's
+    // PuppetHistory if it has one. Any failing CPI reverts the whole
+    // transaction, so it's all-or-nothing. Emits one event per puppet.
+    pub fn pull_strings_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, PullStringsBatch<'info>>,
+        data: Vec<u64>,
+    ) -> Result<()> {
+        require!(!data.is_empty(), MasterError::EmptyBatch);
+        require_gte!(
+            PullStringsBatch::MAX_BATCH_SIZE,
+            data.len(),
+            MasterError::BatchTooLarge
+        );
+
+        let mut remaining_accounts = ctx.remaining_accounts.iter();
+        for (index, value) in data.into_iter().enumerate() {
+            let (puppet_info, authority_info) =
+                match (remaining_accounts.next(), remaining_accounts.next()) {
+                    (Some(puppet_info), Some(authority_info)) => (puppet_info, authority_info),
+                    _ => return err!(MasterError::BatchLengthMismatch),
+                };
+
+            // NOTE try_from() checks owner + discriminator for both
+            let puppet = Account::<Puppet>::try_from(puppet_info)?;
+            // NOTE The puppet program checks it's this puppet's history
+            let history_info = if puppet.has_history {
+                Some(
+                    remaining_accounts
+                        .next()
+                        .ok_or(MasterError::BatchLengthMismatch)?
+                        .clone(),
+                )
+            } else {
+                None
+            };
+            let authority = Account::<PuppetAuthority>::try_from(authority_info)?;
+            require_keys_eq!(
+                authority.puppet,
+                puppet.key(),
+                MasterError::PuppetAuthorityMismatch
+            );
+            require_keys_eq!(
+                authority.owner,
+                ctx.accounts.owner.key(),
+                MasterError::Unauthorized
+            );
+
+            let result = puppet_program::cpi::set_data(
+                CpiContext::new(
+                    ctx.accounts.puppet_program.to_account_info(),
+                    SetData {
+                        puppet: puppet_info.clone(),
+                        authority: authority_info.clone(), // <-- PDA
+                    },
+                )
+                .with_signer(&[&authority.seeds()])
+                .with_remaining_accounts(history_info.into_iter().collect()),
+                value,
+            )?;
+            let set_data_result = result.get();
+
+            emit!(PuppetStringsPulled {
+                index: index as u8,
+                puppet: puppet.key(),
+                previous_data: set_data_result.previous_data,
+                new_data: set_data_result.new_data,
+            });
+        }
+        require!(
+            remaining_accounts.next().is_none(),
+            MasterError::BatchLengthMismatch
+        );
+
+        Ok(())
+    }
+
+    // === CPI into Puppet Program (variable-length payload)
+    // NOTE 'payer' covers the puppet's realloc rent (and receives refunds),
+    // since our 'authority' PDA holds data and can't pay.
+    pub fn pull_payload(ctx: Context<PullPayload>, payload: Vec<u8>) -> Result<()> {
+        puppet_program::cpi::set_payload(
+            ctx.accounts
+                .set_payload_ctx()
+                .with_signer(&[&ctx.accounts.authority.seeds()]),
+            payload,
+        )
+    }
+
+    // === Hand a puppet over to another controller
+    // NOTE Only the PuppetAuthority owner may release. Our PDA signs the
+    // puppet's transfer_puppet_authority(), then the now-stale
+    // PuppetAuthority account is closed and its rent sent to 'receiver'.
+    pub fn release_puppet(ctx: Context<ReleasePuppet>, new_authority: Pubkey) -> Result<()> {
+        puppet_program::cpi::transfer_puppet_authority(
+            ctx.accounts
+                .transfer_puppet_authority_ctx()
+                .with_signer(&[&ctx.accounts.authority.seeds()]),
+            new_authority,
+        )
+    }
+
+    // === Close a retired puppet along with its PuppetAuthority
+    // NOTE The puppet's PuppetHistory/PuppetDelegate accounts (if any) are
+    // passed in remaining accounts and forwarded to close_puppet().
+    pub fn retire_puppet<'info>(ctx: Context<'_, '_, '_, 'info, RetirePuppet<'info>>) -> Result<()> {
+        puppet_program::cpi::close_puppet(
+            ctx.accounts
+                .close_puppet_ctx()
+                .with_signer(&[&ctx.accounts.authority.seeds()])
+                .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
         )
     }
 
+    // === Allowlisted generic CPI router
+    // NOTE Only master's upgrade authority can create the allowlist and
+    // become its admin. Only the admin can manage the allowlist and route CPIs.
+    pub fn initialize_program_allowlist(ctx: Context<InitializeProgramAllowlist>) -> Result<()> {
+        let program_allowlist = &mut ctx.accounts.program_allowlist;
+        program_allowlist.admin = ctx.accounts.admin.key();
+        program_allowlist.programs = Vec::new();
+        program_allowlist.bump = *ctx
+            .bumps
+            .get("program_allowlist")
+            .ok_or(MasterError::BumpNotFound)?;
+        program_allowlist.router_signer_bump = *ctx
+            .bumps
+            .get("router_signer")
+            .ok_or(MasterError::BumpNotFound)?;
+        Ok(())
+    }
+
+    pub fn add_allowed_program(
+        ctx: Context<UpdateProgramAllowlist>,
+        allowed_program_id: Pubkey,
+    ) -> Result<()> {
+        // NOTE Routing back into master would let the router sign
+        // for master's own instructions
+        require_keys_neq!(allowed_program_id, crate::ID, MasterError::CannotAllowlistSelf);
+
+        let program_allowlist = &mut ctx.accounts.program_allowlist;
+        require!(
+            !program_allowlist.programs.contains(&allowed_program_id),
+            MasterError::ProgramAlreadyAllowlisted
+        );
+        require_gt!(
+            ProgramAllowlist::MAX_PROGRAMS,
+            program_allowlist.programs.len(),
+            MasterError::AllowlistFull
+        );
+        program_allowlist.programs.push(allowed_program_id);
+        Ok(())
+    }
+
+    pub fn remove_allowed_program(
+        ctx: Context<UpdateProgramAllowlist>,
+        allowed_program_id: Pubkey,
+    ) -> Result<()> {
+        let program_allowlist = &mut ctx.accounts.program_allowlist;
+        let index = program_allowlist
+            .programs
+            .iter()
+            .position(|allowed| allowed == &allowed_program_id)
+            .ok_or(MasterError::ProgramNotAllowlisted)?;
+        program_allowlist.programs.swap_remove(index);
+        Ok(())
+    }
+
+    // NOTE Forwards 'data' as-is to 'target_program' with remaining_accounts
+    // as the instruction's accounts (same order, same writable/signer flags).
+    // If the router_signer PDA appears in remaining_accounts, it's marked
+    // as a signer and master signs for it. Any return data is forwarded.
+    pub fn route_cpi<'info>(
+        ctx: Context<'_, '_, '_, 'info, RouteCpi<'info>>,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        let target_program_id = ctx.accounts.target_program.key();
+        require!(
+            ctx.accounts
+                .program_allowlist
+                .programs
+                .contains(&target_program_id),
+            MasterError::ProgramNotAllowlisted
+        );
+
+        let router_signer_key = ctx.accounts.router_signer.key();
+        let accounts = ctx
+            .remaining_accounts
+            .iter()
+            .map(|account_info| AccountMeta {
+                pubkey: account_info.key(),
+                is_signer: account_info.is_signer || account_info.key() == router_signer_key,
+                is_writable: account_info.is_writable,
+            })
+            .collect();
+        let instruction = Instruction {
+            program_id: target_program_id,
+            accounts,
+            data,
+        };
+
+        let mut account_infos = ctx.remaining_accounts.to_vec();
+        account_infos.push(ctx.accounts.target_program.to_account_info());
+        invoke_signed(
+            &instruction,
+            &account_infos,
+            &[&ctx.accounts.program_allowlist.router_signer_seeds()],
+        )?;
+
+        if let Some((return_program_id, return_data)) = get_return_data() {
+            if return_program_id == target_program_id {
+                set_return_data(&return_data);
+            }
+        }
+
+        Ok(())
+    }
+
     // === CPI into Dapp Token Manager Program
-    pub fn dapp_token_instruction_handler(
-        ctx: Context<DappTokenInstruction>,
-        authority: Pubkey,
-        supply_amount_per_mint: u64,
+    // U: Renamed from dapp_token_instruction_handler(). No more 'authority'
+    // arg: the DTM's authority is always master's managed_token_authority PDA,
+    // so every admin op on the DTM has to go through master's
+    // *_managed_token*() wrappers below, which only the creator can call.
+    pub fn create_managed_token(
+        ctx: Context<CreateManagedToken>,
+        args: CreateDappTokenManagerArgs,
     ) -> Result<()> {
         // NOTE Inside Callee Program (DTMP), the DTM Account is a PDA,
-        // with prefix, mint, and authority as seeds.
+        // with prefix and mint as seeds (authority is stored, not a seed).
         // Q: Do I need to sign this with seeds? I don't think so, since
         // technically, the CreateDappTokenManager instruction performs
         // the PDA creation and signs the initialize_mint CPI... I just
         // need to pass everything it needs to read or write to.
+        // A: Correct. The DTM PDA signs inside DTMP, and the mint is a
+        // Keypair signer passed through from the client.
+        let managed_token = &mut ctx.accounts.managed_token;
+        managed_token.dapp_token_manager = ctx.accounts.dapp_token_manager.key();
+        managed_token.creator = ctx.accounts.authority_payer.key();
+        managed_token.bump = *ctx
+            .bumps
+            .get("managed_token")
+            .ok_or(MasterError::BumpNotFound)?;
+        managed_token.authority_bump = *ctx
+            .bumps
+            .get("managed_token_authority")
+            .ok_or(MasterError::BumpNotFound)?;
+
         dapp_token_manager_program::cpi::create_dapp_token_manager(
             ctx.accounts.create_dapp_token_manager_ctx(),
-            authority,
-            supply_amount_per_mint,
+            CreateDappTokenManagerArgs {
+                // NOTE Caller-supplied authority is ignored: master's
+                // managed_token_authority PDA always owns managed DTMs
+                authority: ctx.accounts.managed_token_authority.key(),
+                // NOTE Managed tokens stay on the legacy Token program
+                // (token_program below is Program<Token>), so no extensions
+                token_extensions: None,
+                ..args
+            },
         )?;
 
         Ok(())
     }
+
+    // === CPI into Dapp Token Manager Program (mint to user)
+    // NOTE DTMP enforces pause/cooldown/limits/cap and the DTM PDA
+    // signs mint_to(). Master only forwards, for managed DTMs only.
+    pub fn mint_managed_token(ctx: Context<MintManagedToken>) -> Result<()> {
+        dapp_token_manager_program::cpi::mint_dapp_token_supply(
+            ctx.accounts.mint_dapp_token_supply_ctx(),
+        )
+    }
+
+    // === Admin ops on managed DTMs
+    // NOTE Only the ManagedToken creator may call these. Master signs
+    // each CPI as the DTM's authority with the managed_token_authority PDA.
+    pub fn update_managed_token(
+        ctx: Context<ManageToken>,
+        supply_amount_per_mint: Option<u64>,
+        paused: Option<bool>,
+    ) -> Result<()> {
+        dapp_token_manager_program::cpi::update_dapp_token_manager(
+            ctx.accounts
+                .update_dapp_token_manager_ctx()
+                .with_signer(&[&ctx.accounts.managed_token.authority_seeds()]),
+            supply_amount_per_mint,
+            paused,
+        )
+    }
+
+    // NOTE Hands the DTM over to 'new_authority' once it accepts in DTMP.
+    // After that the DTM is no longer managed by master.
+    pub fn propose_managed_token_authority(
+        ctx: Context<ManageToken>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        dapp_token_manager_program::cpi::propose_authority(
+            ctx.accounts
+                .propose_authority_ctx()
+                .with_signer(&[&ctx.accounts.managed_token.authority_seeds()]),
+            new_authority,
+        )
+    }
+
+    // NOTE Takes a DTM back under master's management after its current
+    // authority proposed the managed_token_authority PDA
+    pub fn accept_managed_token_authority(ctx: Context<ManageToken>) -> Result<()> {
+        dapp_token_manager_program::cpi::accept_authority(
+            ctx.accounts
+                .accept_authority_ctx()
+                .with_signer(&[&ctx.accounts.managed_token.authority_seeds()]),
+        )
+    }
+
+    pub fn freeze_managed_token_account(ctx: Context<ManageTokenAccount>) -> Result<()> {
+        dapp_token_manager_program::cpi::freeze_user_account(
+            ctx.accounts
+                .freeze_user_account_ctx()
+                .with_signer(&[&ctx.accounts.managed_token.authority_seeds()]),
+        )
+    }
+
+    pub fn thaw_managed_token_account(ctx: Context<ManageTokenAccount>) -> Result<()> {
+        dapp_token_manager_program::cpi::thaw_user_account(
+            ctx.accounts
+                .thaw_user_account_ctx()
+                .with_signer(&[&ctx.accounts.managed_token.authority_seeds()]),
+        )
+    }
+
+    pub fn update_managed_token_metadata(
+        ctx: Context<ManageTokenMetadata>,
+        token_metadata: TokenMetadataArgs,
+    ) -> Result<()> {
+        dapp_token_manager_program::cpi::update_dapp_token_metadata(
+            ctx.accounts
+                .update_dapp_token_metadata_ctx()
+                .with_signer(&[&ctx.accounts.managed_token.authority_seeds()]),
+            token_metadata,
+        )
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializePuppetAuthority<'info> {
+    // NOTE Puppet must currently be controlled by 'owner'. It's
+    // transferred to the PDA below in the same IX.
+    #[account(
+        mut,
+        constraint = puppet.authority == owner.key() @ MasterError::Unauthorized
+    )]
+    pub puppet: Account<'info, Puppet>,
+    pub puppet_program: Program<'info, PuppetProgram>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = PuppetAuthority::ACCOUNT_SPACE,
+        seeds = [
+            PuppetAuthority::SEED_PREFIX.as_ref(),
+            puppet.key().as_ref(),
+        ],
+        bump
+    )]
+    pub puppet_authority: Account<'info, PuppetAuthority>,
+
+    // NOTE Puppet's current authority. Becomes the PuppetAuthority owner
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializePuppetAuthority<'info> {
+    pub fn transfer_puppet_authority_ctx(
+        &self,
+    ) -> CpiContext<'_, '_, '_, 'info, TransferPuppetAuthority<'info>> {
+        // NOTE No PDA signer needed. 'owner' already signed this IX
+        CpiContext::new(
+            self.puppet_program.to_account_info(),
+            TransferPuppetAuthority {
+                puppet: self.puppet.to_account_info(),
+                authority: self.owner.to_account_info(),
+            },
+        )
+    }
 }
 
 // Instruction validation struct
@@ -124,8 +562,21 @@ pub struct PullStrings<'info> {
     // ---
     // This means we mustn't add a check for it. We just care about allowing
     // master_program to sign so we don't add any additional seeds. Just a bump is passed.
-    /// CHECK: only used as a signing PDA
-    pub authority: UncheckedAccount<'info>, //pub authority: Signer<'info>,
+    // U: Now a per-puppet PDA: [SEED_PREFIX, puppet.key()] with a stored bump.
+    // Still NOT a Signer here (master signs during CPI), but we DO verify it's
+    // our canonical PDA for this puppet.
+    // U: Only the PuppetAuthority owner may pull. Otherwise any wallet
+    // could drive the puppet through master's PDA signature.
+    #[account(
+        seeds = [
+            PuppetAuthority::SEED_PREFIX.as_ref(),
+            puppet.key().as_ref(),
+        ],
+        bump = authority.bump,
+        has_one = owner @ MasterError::Unauthorized,
+    )]
+    pub authority: Account<'info, PuppetAuthority>,
+    pub owner: Signer<'info>,
 }
 
 // NOTE Recommended to move CPI setup in impl block of instruction
@@ -135,6 +586,8 @@ impl<'info> PullStrings<'info> {
         // Build and return the CpiContext object
         // Q: Could I use new_with_signer() since a PDA signs?
         // U: Not in this case since 'bump' is passed as IX data!
+        // U: bump is now stored on PuppetAuthority, but keeping .with_signer()
+        // in the handler so this ctx builder stays reusable.
         CpiContext::new(
             self.puppet_program.to_account_info(), // cpi_program
             // Use accounts context struct (SetData instruction builder) from puppet program
@@ -162,6 +615,194 @@ impl<'info> PullStrings<'info> {
     }
 }
 
+#[derive(Accounts)]
+pub struct InitializeProgramAllowlist<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = ProgramAllowlist::ACCOUNT_SPACE,
+        seeds = [ProgramAllowlist::SEED_PREFIX.as_ref()],
+        bump
+    )]
+    pub program_allowlist: Account<'info, ProgramAllowlist>,
+    /// CHECK: Only used to derive and store the router signer's bump
+    #[account(
+        seeds = [ProgramAllowlist::ROUTER_SIGNER_SEED_PREFIX.as_ref()],
+        bump
+    )]
+    pub router_signer: UncheckedAccount<'info>,
+    #[account(
+        constraint = master_program.programdata_address()? == Some(master_program_data.key())
+            @ MasterError::Unauthorized,
+    )]
+    pub master_program: Program<'info, crate::program::MasterProgram>,
+    // NOTE Stops anyone but the deployer from front-running the init
+    #[account(
+        constraint = master_program_data.upgrade_authority_address == Some(admin.key())
+            @ MasterError::Unauthorized,
+    )]
+    pub master_program_data: Account<'info, ProgramData>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateProgramAllowlist<'info> {
+    #[account(
+        mut,
+        seeds = [ProgramAllowlist::SEED_PREFIX.as_ref()],
+        bump = program_allowlist.bump,
+        has_one = admin @ MasterError::Unauthorized,
+    )]
+    pub program_allowlist: Account<'info, ProgramAllowlist>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RouteCpi<'info> {
+    #[account(
+        seeds = [ProgramAllowlist::SEED_PREFIX.as_ref()],
+        bump = program_allowlist.bump,
+        has_one = admin @ MasterError::Unauthorized,
+    )]
+    pub program_allowlist: Account<'info, ProgramAllowlist>,
+    pub admin: Signer<'info>,
+    /// CHECK: Checked against the allowlist in route_cpi()
+    #[account(executable)]
+    pub target_program: UncheckedAccount<'info>,
+    /// CHECK: PDA master signs with. Pass it in remaining_accounts too
+    /// wherever the target instruction expects it.
+    #[account(
+        seeds = [ProgramAllowlist::ROUTER_SIGNER_SEED_PREFIX.as_ref()],
+        bump = program_allowlist.router_signer_bump,
+    )]
+    pub router_signer: UncheckedAccount<'info>,
+    // NOTE Target instruction's accounts are passed as remaining_accounts
+}
+
+#[derive(Accounts)]
+pub struct PullStringsBatch<'info> {
+    pub puppet_program: Program<'info, PuppetProgram>,
+    // NOTE Must own every PuppetAuthority in the batch (checked in handler)
+    pub owner: Signer<'info>,
+    // NOTE Puppets + their PuppetAuthority PDAs are passed as
+    // remaining_accounts: [puppet_0, authority_0, puppet_1, authority_1, ...]
+    // with each puppet's PuppetHistory right after its authority, if it has one
+}
+
+impl<'info> PullStringsBatch<'info> {
+    // NOTE Each set_data CPI costs a few thousand CUs on top of the
+    // account checks. Keeps a full batch well within the default 200k
+    // CU budget and the transaction size limit.
+    pub const MAX_BATCH_SIZE: usize = 8;
+}
+
+#[derive(Accounts)]
+pub struct PullPayload<'info> {
+    #[account(mut)]
+    pub puppet: Account<'info, Puppet>,
+    pub puppet_program: Program<'info, PuppetProgram>,
+    #[account(
+        seeds = [
+            PuppetAuthority::SEED_PREFIX.as_ref(),
+            puppet.key().as_ref(),
+        ],
+        bump = authority.bump,
+        has_one = owner @ MasterError::Unauthorized,
+    )]
+    pub authority: Account<'info, PuppetAuthority>,
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> PullPayload<'info> {
+    pub fn set_payload_ctx(&self) -> CpiContext<'_, '_, '_, 'info, SetPayload<'info>> {
+        CpiContext::new(
+            self.puppet_program.to_account_info(),
+            SetPayload {
+                puppet: self.puppet.to_account_info(),
+                authority: self.authority.to_account_info(), // <-- PDA
+                payer: self.payer.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+            },
+        )
+    }
+}
+
+#[derive(Accounts)]
+pub struct ReleasePuppet<'info> {
+    #[account(mut)]
+    pub puppet: Account<'info, Puppet>,
+    pub puppet_program: Program<'info, PuppetProgram>,
+    #[account(
+        mut,
+        seeds = [
+            PuppetAuthority::SEED_PREFIX.as_ref(),
+            puppet.key().as_ref(),
+        ],
+        bump = authority.bump,
+        has_one = owner @ MasterError::Unauthorized,
+        close = receiver,
+    )]
+    pub authority: Account<'info, PuppetAuthority>,
+    pub owner: Signer<'info>,
+    /// CHECK: Only receives the closed PuppetAuthority's lamports
+    #[account(mut)]
+    pub receiver: UncheckedAccount<'info>,
+}
+
+impl<'info> ReleasePuppet<'info> {
+    pub fn transfer_puppet_authority_ctx(
+        &self,
+    ) -> CpiContext<'_, '_, '_, 'info, TransferPuppetAuthority<'info>> {
+        CpiContext::new(
+            self.puppet_program.to_account_info(),
+            TransferPuppetAuthority {
+                puppet: self.puppet.to_account_info(),
+                authority: self.authority.to_account_info(), // <-- PDA
+            },
+        )
+    }
+}
+
+#[derive(Accounts)]
+pub struct RetirePuppet<'info> {
+    #[account(mut)]
+    pub puppet: Account<'info, Puppet>,
+    pub puppet_program: Program<'info, PuppetProgram>,
+    #[account(
+        mut,
+        seeds = [
+            PuppetAuthority::SEED_PREFIX.as_ref(),
+            puppet.key().as_ref(),
+        ],
+        bump = authority.bump,
+        has_one = owner @ MasterError::Unauthorized,
+        close = receiver,
+    )]
+    pub authority: Account<'info, PuppetAuthority>,
+    pub owner: Signer<'info>,
+    /// CHECK: Receives both the puppet's and PuppetAuthority's lamports
+    #[account(mut)]
+    pub receiver: UncheckedAccount<'info>,
+}
+
+impl<'info> RetirePuppet<'info> {
+    pub fn close_puppet_ctx(&self) -> CpiContext<'_, '_, '_, 'info, ClosePuppet<'info>> {
+        CpiContext::new(
+            self.puppet_program.to_account_info(),
+            ClosePuppet {
+                puppet: self.puppet.to_account_info(),
+                authority: self.authority.to_account_info(), // <-- PDA
+                receiver: self.receiver.to_account_info(),
+            },
+        )
+    }
+}
+
 // use anchor_lang::prelude::*;
 // use puppet_program::{self, cpi::accounts::SetData, program::PuppetProgram, Puppet};
 
@@ -239,8 +880,7 @@ impl<'info> PullStrings<'info> {
 // }
 
 #[derive(Accounts)]
-#[instruction(authority: Pubkey, supply_amount_per_mint: u64)]
-pub struct DappTokenInstruction<'info> {
+pub struct CreateManagedToken<'info> {
     // ==== CreateDappTokenManager ====
     // Q: How many accounts do I need to pass in from
     // the DTMP? Seems redundant...
@@ -258,8 +898,10 @@ pub struct DappTokenInstruction<'info> {
     pub dapp_token_manager_program: Program<'info, DappTokenManagerProgram>,
 
     // We create the Mint, so making it writable
+    // U: Can't be Account<Mint> since it doesn't exist until the CPI
+    // creates it. DTMP expects a Keypair signer for the mint.
     #[account(mut)]
-    pub mint: Account<'info, Mint>,
+    pub mint: Signer<'info>,
 
     // Q: Do I just copy everything to 'init' dapp_token_manager?
     // Q: Is this a time for UncheckedAccount, since at this point
@@ -274,29 +916,52 @@ pub struct DappTokenInstruction<'info> {
     // Caller to Callee. If this is correct, then I'll have
     // to provide the seeds using .with_signer() I think...
     // REF: InitFarm -- init_bank_ctx()
+    // A: UncheckedAccount. It's a PDA of DTMP, so it can never be a Signer
+    // here (only DTMP can sign for it). DTMP validates the seeds and inits it.
+    /// CHECK: Validated (seeds) and initialized in CPI
     #[account(mut)]
-    pub dapp_token_manager: Signer<'info>,
+    pub dapp_token_manager: UncheckedAccount<'info>,
 
     // Q: What about 'authority' and 'supply_amount_per_mint'??
     // Do I just pass them in the handler or something?
-    #[account(mut)]
-    pub authority_payer: Signer<'info>,
+    // A: IX data. 'authority' is always this PDA though.
+    /// CHECK: Master PDA used as the DTM's authority. Never holds data
+    #[account(
+        seeds = [MANAGED_TOKEN_AUTHORITY_SEED_PREFIX.as_ref()],
+        bump
+    )]
+    pub managed_token_authority: UncheckedAccount<'info>,
 
-    pub rent: Sysvar<'info, Rent>,
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
+    // NOTE Records 'authority_payer' as the creator, who alone may run
+    // admin ops on the DTM through master
+    #[account(
+        init,
+        payer = authority_payer,
+        space = ManagedToken::ACCOUNT_SPACE,
+        seeds = [
+            ManagedToken::SEED_PREFIX.as_ref(),
+            dapp_token_manager.key().as_ref(),
+        ],
+        bump
+    )]
+    pub managed_token: Account<'info, ManagedToken>,
 
-    // ==== MintDappTokenSupply Unique Accounts ====
     #[account(mut)]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub authority_payer: Signer<'info>,
 
+    // NOTE Optional, only needed when 'token_metadata' is Some
+    /// CHECK: Metaplex metadata PDA. Validated in CPI
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub metadata: Option<UncheckedAccount<'info>>,
 
-    pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+    pub token_program: Program<'info, Token>,
+    /// CHECK: Token Metadata Program. Validated in CPI
+    pub token_metadata_program: Option<UncheckedAccount<'info>>,
+    pub system_program: Program<'info, System>,
 }
 
-impl<'info> DappTokenInstruction<'info> {
+impl<'info> CreateManagedToken<'info> {
     fn create_dapp_token_manager_ctx(
         &self,
     ) -> CpiContext<'_, '_, '_, 'info, CreateDappTokenManager<'info>> {
@@ -307,13 +972,60 @@ impl<'info> DappTokenInstruction<'info> {
                 mint: self.mint.to_account_info(),
                 dapp_token_manager: self.dapp_token_manager.to_account_info(),
                 authority_payer: self.authority_payer.to_account_info(),
+                metadata: self.metadata.as_ref().map(|m| m.to_account_info()),
                 rent: self.rent.to_account_info(),
                 token_program: self.token_program.to_account_info(),
+                token_metadata_program: self
+                    .token_metadata_program
+                    .as_ref()
+                    .map(|p| p.to_account_info()),
                 system_program: self.system_program.to_account_info(),
             },
         )
     }
+}
+
+#[derive(Accounts)]
+pub struct MintManagedToken<'info> {
+    pub dapp_token_manager_program: Program<'info, DappTokenManagerProgram>,
 
+    /// CHECK: Validated against dapp_token_manager.mint in CPI
+    #[account(mut)]
+    pub mint: UncheckedAccount<'info>,
+
+    // NOTE Only DTMs created through create_managed_token()
+    #[account(
+        mut,
+        constraint = dapp_token_manager.authority == managed_token_authority.key() @ MasterError::NotManagedToken
+    )]
+    pub dapp_token_manager: Account<'info, DappTokenManager>,
+
+    /// CHECK: Master PDA used as the DTM's authority. Never holds data
+    #[account(
+        seeds = [MANAGED_TOKEN_AUTHORITY_SEED_PREFIX.as_ref()],
+        bump
+    )]
+    pub managed_token_authority: UncheckedAccount<'info>,
+
+    // U: Can't be Account<TokenAccount> since the ATA may not exist yet.
+    /// CHECK: User's ATA. Validated and init_if_needed in CPI
+    #[account(mut)]
+    pub user_token_account: UncheckedAccount<'info>,
+
+    /// CHECK: Validated (seeds) and init_if_needed in CPI
+    #[account(mut)]
+    pub user_mint_record: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+    pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MintManagedToken<'info> {
     fn mint_dapp_token_supply_ctx(
         &self,
     ) -> CpiContext<'_, '_, '_, 'info, MintDappTokenSupply<'info>> {
@@ -324,6 +1036,7 @@ impl<'info> DappTokenInstruction<'info> {
                 user_token_account: self.user_token_account.to_account_info(),
                 mint: self.mint.to_account_info(),
                 dapp_token_manager: self.dapp_token_manager.to_account_info(),
+                user_mint_record: self.user_mint_record.to_account_info(),
                 user: self.user.to_account_info(),
                 rent: self.rent.to_account_info(),
                 associated_token_program: self.associated_token_program.to_account_info(),
@@ -333,3 +1046,337 @@ impl<'info> DappTokenInstruction<'info> {
         )
     }
 }
+
+#[derive(Accounts)]
+pub struct ManageToken<'info> {
+    pub dapp_token_manager_program: Program<'info, DappTokenManagerProgram>,
+
+    /// CHECK: Validated (seeds) in CPI
+    #[account(mut)]
+    pub dapp_token_manager: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [
+            ManagedToken::SEED_PREFIX.as_ref(),
+            dapp_token_manager.key().as_ref(),
+        ],
+        bump = managed_token.bump,
+        has_one = creator @ MasterError::Unauthorized,
+    )]
+    pub managed_token: Account<'info, ManagedToken>,
+
+    /// CHECK: Master PDA used as the DTM's authority. Never holds data
+    #[account(
+        seeds = [MANAGED_TOKEN_AUTHORITY_SEED_PREFIX.as_ref()],
+        bump = managed_token.authority_bump
+    )]
+    pub managed_token_authority: UncheckedAccount<'info>,
+
+    pub creator: Signer<'info>,
+}
+
+impl<'info> ManageToken<'info> {
+    fn update_dapp_token_manager_ctx(
+        &self,
+    ) -> CpiContext<'_, '_, '_, 'info, UpdateDappTokenManager<'info>> {
+        CpiContext::new(
+            self.dapp_token_manager_program.to_account_info(),
+            UpdateDappTokenManager {
+                dapp_token_manager: self.dapp_token_manager.to_account_info(),
+                authority: self.managed_token_authority.to_account_info(), // <-- PDA
+            },
+        )
+    }
+
+    fn propose_authority_ctx(&self) -> CpiContext<'_, '_, '_, 'info, ProposeAuthority<'info>> {
+        CpiContext::new(
+            self.dapp_token_manager_program.to_account_info(),
+            ProposeAuthority {
+                dapp_token_manager: self.dapp_token_manager.to_account_info(),
+                authority: self.managed_token_authority.to_account_info(), // <-- PDA
+            },
+        )
+    }
+
+    fn accept_authority_ctx(&self) -> CpiContext<'_, '_, '_, 'info, AcceptAuthority<'info>> {
+        CpiContext::new(
+            self.dapp_token_manager_program.to_account_info(),
+            AcceptAuthority {
+                dapp_token_manager: self.dapp_token_manager.to_account_info(),
+                pending_authority: self.managed_token_authority.to_account_info(), // <-- PDA
+            },
+        )
+    }
+}
+
+#[derive(Accounts)]
+pub struct ManageTokenAccount<'info> {
+    pub dapp_token_manager_program: Program<'info, DappTokenManagerProgram>,
+
+    /// CHECK: Validated (seeds) in CPI
+    pub dapp_token_manager: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [
+            ManagedToken::SEED_PREFIX.as_ref(),
+            dapp_token_manager.key().as_ref(),
+        ],
+        bump = managed_token.bump,
+        has_one = creator @ MasterError::Unauthorized,
+    )]
+    pub managed_token: Account<'info, ManagedToken>,
+
+    /// CHECK: Master PDA used as the DTM's authority. Never holds data
+    #[account(
+        seeds = [MANAGED_TOKEN_AUTHORITY_SEED_PREFIX.as_ref()],
+        bump = managed_token.authority_bump
+    )]
+    pub managed_token_authority: UncheckedAccount<'info>,
+
+    pub creator: Signer<'info>,
+
+    /// CHECK: Validated against dapp_token_manager.mint in CPI
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: Token account to freeze/thaw. Validated in CPI
+    #[account(mut)]
+    pub user_token_account: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ManageTokenAccount<'info> {
+    fn freeze_user_account_ctx(&self) -> CpiContext<'_, '_, '_, 'info, FreezeUserAccount<'info>> {
+        CpiContext::new(
+            self.dapp_token_manager_program.to_account_info(),
+            FreezeUserAccount {
+                user_token_account: self.user_token_account.to_account_info(),
+                mint: self.mint.to_account_info(),
+                dapp_token_manager: self.dapp_token_manager.to_account_info(),
+                authority: self.managed_token_authority.to_account_info(), // <-- PDA
+                token_program: self.token_program.to_account_info(),
+            },
+        )
+    }
+
+    fn thaw_user_account_ctx(&self) -> CpiContext<'_, '_, '_, 'info, ThawUserAccount<'info>> {
+        CpiContext::new(
+            self.dapp_token_manager_program.to_account_info(),
+            ThawUserAccount {
+                user_token_account: self.user_token_account.to_account_info(),
+                mint: self.mint.to_account_info(),
+                dapp_token_manager: self.dapp_token_manager.to_account_info(),
+                authority: self.managed_token_authority.to_account_info(), // <-- PDA
+                token_program: self.token_program.to_account_info(),
+            },
+        )
+    }
+}
+
+#[derive(Accounts)]
+pub struct ManageTokenMetadata<'info> {
+    pub dapp_token_manager_program: Program<'info, DappTokenManagerProgram>,
+
+    /// CHECK: Validated (seeds) in CPI
+    pub dapp_token_manager: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [
+            ManagedToken::SEED_PREFIX.as_ref(),
+            dapp_token_manager.key().as_ref(),
+        ],
+        bump = managed_token.bump,
+        has_one = creator @ MasterError::Unauthorized,
+    )]
+    pub managed_token: Account<'info, ManagedToken>,
+
+    /// CHECK: Master PDA used as the DTM's authority. Never holds data
+    #[account(
+        seeds = [MANAGED_TOKEN_AUTHORITY_SEED_PREFIX.as_ref()],
+        bump = managed_token.authority_bump
+    )]
+    pub managed_token_authority: UncheckedAccount<'info>,
+
+    pub creator: Signer<'info>,
+
+    /// CHECK: Metaplex metadata PDA. Validated in CPI
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Token Metadata Program. Validated in CPI
+    pub token_metadata_program: UncheckedAccount<'info>,
+}
+
+impl<'info> ManageTokenMetadata<'info> {
+    fn update_dapp_token_metadata_ctx(
+        &self,
+    ) -> CpiContext<'_, '_, '_, 'info, UpdateDappTokenMetadata<'info>> {
+        CpiContext::new(
+            self.dapp_token_manager_program.to_account_info(),
+            UpdateDappTokenMetadata {
+                dapp_token_manager: self.dapp_token_manager.to_account_info(),
+                metadata: self.metadata.to_account_info(),
+                authority: self.managed_token_authority.to_account_info(), // <-- PDA
+                token_metadata_program: self.token_metadata_program.to_account_info(),
+            },
+        )
+    }
+}
+
+// NOTE Master's own return type for pull_strings(), mirroring
+// puppet_program::SetDataResult so it's part of master's IDL.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PullStringsResult {
+    pub previous_data: u64,
+    pub new_data: u64,
+}
+
+impl From<SetDataResult> for PullStringsResult {
+    fn from(result: SetDataResult) -> Self {
+        PullStringsResult {
+            previous_data: result.previous_data,
+            new_data: result.new_data,
+        }
+    }
+}
+
+// NOTE Per-puppet signing PDA for master_program. Stored as a data
+// account so the canonical bump is saved once (initialize_puppet_authority)
+// instead of being passed in (and trusted) on every pull_strings().
+#[account]
+#[derive(Default, Debug)]
+pub struct PuppetAuthority {
+    // 8 bytes for Discrimator
+    pub puppet: Pubkey, // 32 bytes
+    pub owner: Pubkey,  // 32 bytes. Who may release/retire the puppet
+    pub bump: u8,       // 1 byte
+}
+
+const DISCRIMINATOR_LENGTH: usize = 8;
+const PUPPET_LENGTH: usize = 32; // Pubkey
+const OWNER_LENGTH: usize = 32; // Pubkey
+const BUMP_LENGTH: usize = 1;
+
+impl PuppetAuthority {
+    pub const ACCOUNT_SPACE: usize = DISCRIMINATOR_LENGTH + PUPPET_LENGTH + OWNER_LENGTH + BUMP_LENGTH;
+
+    pub const SEED_PREFIX: &'static str = "puppet-authority";
+
+    // NOTE Use slice::from_ref() for the bump to avoid the
+    // 'temporary value created' error with &[self.bump]
+    pub fn seeds(&self) -> [&[u8]; 3] {
+        [
+            Self::SEED_PREFIX.as_bytes(),
+            self.puppet.as_ref(),
+            std::slice::from_ref(&self.bump),
+        ]
+    }
+}
+
+// NOTE Seeds of the master PDA set as 'authority' on managed DTMs
+pub const MANAGED_TOKEN_AUTHORITY_SEED_PREFIX: &str = "managed-token-authority";
+
+// NOTE One per DTM created through create_managed_token(). All managed
+// DTMs share the managed_token_authority PDA as their authority, so this
+// records who may run admin ops on each one.
+#[account]
+#[derive(Default, Debug)]
+pub struct ManagedToken {
+    // 8 bytes for Discrimator
+    pub dapp_token_manager: Pubkey, // 32 bytes
+    pub creator: Pubkey,            // 32 bytes
+    pub bump: u8,                   // 1 byte
+    pub authority_bump: u8,         // 1 byte. managed_token_authority's bump
+}
+
+const DAPP_TOKEN_MANAGER_LENGTH: usize = 32; // Pubkey
+const CREATOR_LENGTH: usize = 32; // Pubkey
+
+impl ManagedToken {
+    pub const ACCOUNT_SPACE: usize = DISCRIMINATOR_LENGTH
+        + DAPP_TOKEN_MANAGER_LENGTH
+        + CREATOR_LENGTH
+        + BUMP_LENGTH
+        + BUMP_LENGTH;
+
+    pub const SEED_PREFIX: &'static str = "managed-token";
+
+    pub fn authority_seeds(&self) -> [&[u8]; 2] {
+        [
+            MANAGED_TOKEN_AUTHORITY_SEED_PREFIX.as_bytes(),
+            std::slice::from_ref(&self.authority_bump),
+        ]
+    }
+}
+
+// NOTE Singleton PDA of programs route_cpi() may invoke
+#[account]
+#[derive(Default, Debug)]
+pub struct ProgramAllowlist {
+    // 8 bytes for Discrimator
+    pub admin: Pubkey,          // 32 bytes
+    pub programs: Vec<Pubkey>,  // 4 + 32 * MAX_PROGRAMS bytes
+    pub bump: u8,               // 1 byte
+    pub router_signer_bump: u8, // 1 byte
+}
+
+const ADMIN_LENGTH: usize = 32; // Pubkey
+const VEC_PREFIX_LENGTH: usize = 4; // Stores the length of the Vec
+const PROGRAM_ID_LENGTH: usize = 32; // Pubkey
+
+impl ProgramAllowlist {
+    pub const MAX_PROGRAMS: usize = 16;
+
+    pub const ACCOUNT_SPACE: usize = DISCRIMINATOR_LENGTH
+        + ADMIN_LENGTH
+        + VEC_PREFIX_LENGTH
+        + PROGRAM_ID_LENGTH * Self::MAX_PROGRAMS
+        + BUMP_LENGTH
+        + BUMP_LENGTH;
+
+    pub const SEED_PREFIX: &'static str = "program-allowlist";
+
+    pub const ROUTER_SIGNER_SEED_PREFIX: &'static str = "router-signer";
+
+    pub fn router_signer_seeds(&self) -> [&[u8]; 2] {
+        [
+            Self::ROUTER_SIGNER_SEED_PREFIX.as_bytes(),
+            std::slice::from_ref(&self.router_signer_bump),
+        ]
+    }
+}
+
+#[event]
+pub struct PuppetStringsPulled {
+    // NOTE Position in the pull_strings_batch() 'data' vector
+    pub index: u8,
+    pub puppet: Pubkey,
+    pub previous_data: u64,
+    pub new_data: u64,
+}
+
+#[error_code]
+pub enum MasterError {
+    #[msg("Puppet authority is not this puppet's master PDA")]
+    PuppetAuthorityMismatch,
+    #[msg("Bump not found")]
+    BumpNotFound,
+    #[msg("Signer is not the puppet authority's owner")]
+    Unauthorized,
+    #[msg("Batch must contain at least one puppet")]
+    EmptyBatch,
+    #[msg("Batch exceeds the maximum number of puppets")]
+    BatchTooLarge,
+    #[msg("Expected one puppet, puppet authority and (if it has one) history account per value")]
+    BatchLengthMismatch,
+    #[msg("Program is not in the allowlist")]
+    ProgramNotAllowlisted,
+    #[msg("Program is already in the allowlist")]
+    ProgramAlreadyAllowlisted,
+    #[msg("Program allowlist is full")]
+    AllowlistFull,
+    #[msg("Master program can't route CPIs to itself")]
+    CannotAllowlistSelf,
+    #[msg("Dapp token manager is not managed by master")]
+    NotManagedToken,
+}
@@ -1,5 +1,7 @@
 // https://book.anchor-lang.com/anchor_in_depth/CPIs.html
 use anchor_lang::prelude::*;
+use bytemuck::{Pod, Zeroable};
+use std::mem::size_of;
 
 declare_id!("H1nQekf9pqzhV6B8v5CKehEF3g9x2D9PTpXFXWQYUfmk");
 
@@ -11,23 +13,198 @@ pub mod puppet_program {
         Ok(())
     }
 
-    pub fn set_data(ctx: Context<SetData>, data: u64) -> Result<()> {
-        let puppet = &mut ctx.accounts.puppet;
-        puppet.data = data;
-        Ok(())
-    }
     // Q: How to return values from handler functions?
     // A: Use Solana's set_return_data and get_return_data syscalls!
     // This data can be used in CPI callers and clients.
     // REF: https://book.anchor-lang.com/anchor_in_depth/CPIs.html#returning-values-from-handler-functions
-    // You just need to specify the return data inside Result<u64>
+    // You just need to specify the return data inside Result<T>
     // When you don't use '()' return type, Anchor calls set_return_data()
     // The return from a CPI call is wrapped in a struct to allow lazy retrieval of data
-    // pub fn set_data(ctx: Context<SetData>, data: u64) -> Result<u64> {
-    //     let puppet = &mut ctx.accounts.puppet;
-    //     puppet.data = data;
-    //     Ok(data)
-    // }
+    // NOTE The type returned must implement AnchorSerialize and AnchorDeserialize
+    // U: 'authority' may also be a delegate. Pass its PuppetDelegate
+    // record in remaining accounts (see authorize_writer())
+    pub fn set_data(ctx: Context<SetData>, data: u64) -> Result<SetDataResult> {
+        authorize_writer(
+            &ctx.accounts.puppet,
+            &ctx.accounts.authority,
+            ctx.remaining_accounts,
+            data,
+        )?;
+
+        let puppet = &mut ctx.accounts.puppet;
+        let previous_data = puppet.data;
+        puppet.data = data;
+        puppet.version = puppet.version.checked_add(1).ok_or(PuppetError::MathOverflow)?;
+
+        // NOTE PuppetHistory is passed in remaining accounts. Required
+        // once initialize_puppet_history() has run for this puppet
+        record_history(
+            ctx.remaining_accounts,
+            puppet,
+            ctx.accounts.authority.key(),
+            previous_data,
+            data,
+        )?;
+
+        Ok(SetDataResult {
+            previous_data,
+            new_data: data,
+        })
+    }
+
+    // NOTE Compare-and-swap variant of set_data(). Only writes when the
+    // current value still equals 'expected', so concurrent controllers
+    // can't silently clobber each other's updates.
+    pub fn compare_and_set_data(
+        ctx: Context<SetData>,
+        expected: u64,
+        new: u64,
+    ) -> Result<SetDataResult> {
+        authorize_writer(
+            &ctx.accounts.puppet,
+            &ctx.accounts.authority,
+            ctx.remaining_accounts,
+            new,
+        )?;
+
+        let puppet = &mut ctx.accounts.puppet;
+        require_eq!(puppet.data, expected, PuppetError::DataMismatch);
+
+        let previous_data = puppet.data;
+        puppet.data = new;
+        puppet.version = puppet.version.checked_add(1).ok_or(PuppetError::MathOverflow)?;
+
+        record_history(
+            ctx.remaining_accounts,
+            puppet,
+            ctx.accounts.authority.key(),
+            previous_data,
+            new,
+        )?;
+
+        Ok(SetDataResult {
+            previous_data,
+            new_data: new,
+        })
+    }
+
+    // NOTE Store an arbitrary byte payload (up to MAX_PAYLOAD_LENGTH).
+    // The realloc constraint resizes the account to fit: 'payer' tops up
+    // rent when the payload grows and gets the difference back when it shrinks.
+    pub fn set_payload(ctx: Context<SetPayload>, payload: Vec<u8>) -> Result<()> {
+        let puppet = &mut ctx.accounts.puppet;
+        puppet.payload = payload;
+        puppet.version = puppet.version.checked_add(1).ok_or(PuppetError::MathOverflow)?;
+        Ok(())
+    }
+
+    // === Optional companion ring buffer of data updates
+    // NOTE Once created, it must be passed in remaining accounts to
+    // set_data()/compare_and_set_data(), so every write is recorded.
+    pub fn initialize_puppet_history(
+        ctx: Context<InitializePuppetHistory>,
+        capacity: u64,
+    ) -> Result<()> {
+        ctx.accounts.puppet.has_history = true;
+        let mut history = ctx.accounts.history.load_init()?;
+        history.puppet = ctx.accounts.puppet.key();
+        history.capacity = capacity;
+        history.bump = *ctx
+            .bumps
+            .get("history")
+            .ok_or(PuppetError::BumpNotFound)?;
+        Ok(())
+    }
+
+    // NOTE Resizing always clears the recorded entries. Pass the current
+    // capacity to just clear.
+    pub fn resize_puppet_history(ctx: Context<ResizePuppetHistory>, capacity: u64) -> Result<()> {
+        {
+            // NOTE Update capacity before mapping the entries, since the
+            // account was already realloc'd to the new size
+            let mut history = ctx.accounts.history.load_mut()?;
+            history.capacity = capacity;
+            history.head = 0;
+            history.len = 0;
+        }
+        let history_info = ctx.accounts.history.to_account_info();
+        let mut data = history_info.try_borrow_mut_data()?;
+        let (_, entries) = PuppetHistory::split_mut(&mut data)?;
+        entries.fill(HistoryEntry::default());
+        Ok(())
+    }
+
+    // === Scoped write access for other wallets or programs' PDAs
+    // NOTE The delegate may set_data() within [min_data, max_data],
+    // and only up to 'valid_until_slot' (inclusive) if set.
+    // Delegations are tied to the granting authority, so
+    // transfer_puppet_authority() implicitly revokes them.
+    pub fn grant_delegate(
+        ctx: Context<GrantDelegate>,
+        delegate: Pubkey,
+        min_data: u64,
+        max_data: u64,
+        valid_until_slot: Option<u64>,
+    ) -> Result<()> {
+        require_gte!(max_data, min_data, PuppetError::InvalidDelegateRange);
+
+        let delegate_record = &mut ctx.accounts.delegate_record;
+        delegate_record.puppet = ctx.accounts.puppet.key();
+        delegate_record.authority = ctx.accounts.authority.key();
+        delegate_record.delegate = delegate;
+        delegate_record.min_data = min_data;
+        delegate_record.max_data = max_data;
+        delegate_record.valid_until_slot = valid_until_slot;
+        delegate_record.bump = *ctx
+            .bumps
+            .get("delegate_record")
+            .ok_or(PuppetError::BumpNotFound)?;
+        Ok(())
+    }
+
+    pub fn revoke_delegate(_ctx: Context<RevokeDelegate>) -> Result<()> {
+        Ok(())
+    }
+
+    // NOTE Hand the puppet over to a new controller (keypair or another
+    // program's PDA). Must be signed by the current authority.
+    pub fn transfer_puppet_authority(
+        ctx: Context<TransferPuppetAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.puppet.authority = new_authority;
+        Ok(())
+    }
+
+    // NOTE Retire the puppet. Anchor's close constraint zeroes the data
+    // and sends the rent lamports to 'receiver'.
+    // U: Its PuppetHistory and PuppetDelegate PDAs can't be closed once the
+    // puppet is gone, so they're closed here too. Pass them in remaining
+    // accounts. The history is required if has_history is set.
+    pub fn close_puppet<'info>(ctx: Context<'_, '_, '_, 'info, ClosePuppet<'info>>) -> Result<()> {
+        let puppet = &ctx.accounts.puppet;
+        let receiver = ctx.accounts.receiver.to_account_info();
+
+        match find_remaining_account::<PuppetHistory>(ctx.remaining_accounts) {
+            Some(history_info) => {
+                let history = AccountLoader::<PuppetHistory>::try_from(history_info)?;
+                require_keys_eq!(history.load()?.puppet, puppet.key(), PuppetError::HistoryMismatch);
+                history.close(receiver.clone())?;
+            }
+            None => require!(!puppet.has_history, PuppetError::HistoryRequired),
+        }
+
+        for delegate_info in ctx
+            .remaining_accounts
+            .iter()
+            .filter(|account_info| is_remaining_account::<PuppetDelegate>(account_info))
+        {
+            let delegate_record = Account::<PuppetDelegate>::try_from(delegate_info)?;
+            require_keys_eq!(delegate_record.puppet, puppet.key(), PuppetError::DelegateMismatch);
+            delegate_record.close(receiver.clone())?;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Accounts)]
@@ -42,7 +219,9 @@ pub struct Initialize<'info> {
 #[derive(Accounts)]
 pub struct SetData<'info> {
     // NOTE has_one checks that puppet.authority = authority.key()
-    #[account(mut, has_one = authority)]
+    // U: No more has_one. authorize_writer() accepts the puppet authority
+    // OR a delegate with a valid PuppetDelegate record.
+    #[account(mut)]
     pub puppet: Account<'info, Puppet>,
     // Q: If masterProgram uses a PDA for PullStrings 'authority' CPI account,
     // will type Signer still work?
@@ -50,16 +229,383 @@ pub struct SetData<'info> {
     pub authority: Signer<'info>,
 }
 
+#[derive(Accounts)]
+#[instruction(payload: Vec<u8>)]
+pub struct SetPayload<'info> {
+    #[account(
+        mut,
+        has_one = authority,
+        constraint = payload.len() <= Puppet::MAX_PAYLOAD_LENGTH @ PuppetError::PayloadTooLarge,
+        realloc = Puppet::space(payload.len()),
+        realloc::payer = payer,
+        realloc::zero = false,
+    )]
+    pub puppet: Account<'info, Puppet>,
+    pub authority: Signer<'info>,
+    // NOTE Separate from 'authority' since a PDA authority can't pay rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(capacity: u64)]
+pub struct InitializePuppetHistory<'info> {
+    #[account(mut, has_one = authority)]
+    pub puppet: Account<'info, Puppet>,
+    pub authority: Signer<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = PuppetHistory::space(capacity),
+        seeds = [
+            PuppetHistory::SEED_PREFIX.as_ref(),
+            puppet.key().as_ref(),
+        ],
+        bump,
+        constraint = PuppetHistory::is_valid_capacity(capacity) @ PuppetError::InvalidHistoryCapacity,
+    )]
+    pub history: AccountLoader<'info, PuppetHistory>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(capacity: u64)]
+pub struct ResizePuppetHistory<'info> {
+    #[account(has_one = authority)]
+    pub puppet: Account<'info, Puppet>,
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            PuppetHistory::SEED_PREFIX.as_ref(),
+            puppet.key().as_ref(),
+        ],
+        bump = history.load()?.bump,
+        constraint = PuppetHistory::is_valid_capacity(capacity) @ PuppetError::InvalidHistoryCapacity,
+        realloc = PuppetHistory::space(capacity),
+        realloc::payer = payer,
+        realloc::zero = true,
+    )]
+    pub history: AccountLoader<'info, PuppetHistory>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(delegate: Pubkey)]
+pub struct GrantDelegate<'info> {
+    #[account(has_one = authority)]
+    pub puppet: Account<'info, Puppet>,
+    pub authority: Signer<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = PuppetDelegate::ACCOUNT_SPACE,
+        seeds = [
+            PuppetDelegate::SEED_PREFIX.as_ref(),
+            puppet.key().as_ref(),
+            delegate.as_ref(),
+        ],
+        bump
+    )]
+    pub delegate_record: Account<'info, PuppetDelegate>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeDelegate<'info> {
+    #[account(has_one = authority)]
+    pub puppet: Account<'info, Puppet>,
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            PuppetDelegate::SEED_PREFIX.as_ref(),
+            puppet.key().as_ref(),
+            delegate_record.delegate.as_ref(),
+        ],
+        bump = delegate_record.bump,
+        close = receiver,
+    )]
+    pub delegate_record: Account<'info, PuppetDelegate>,
+    /// CHECK: Only receives the closed account's lamports
+    #[account(mut)]
+    pub receiver: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TransferPuppetAuthority<'info> {
+    #[account(mut, has_one = authority)]
+    pub puppet: Account<'info, Puppet>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClosePuppet<'info> {
+    #[account(mut, has_one = authority, close = receiver)]
+    pub puppet: Account<'info, Puppet>,
+    pub authority: Signer<'info>,
+    /// CHECK: Only receives the closed account's lamports
+    #[account(mut)]
+    pub receiver: UncheckedAccount<'info>,
+}
+
+// NOTE Return data of set_data(). Callers read it via
+// cpi::set_data(..)?.get(), clients via simulate/view.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SetDataResult {
+    pub previous_data: u64,
+    pub new_data: u64,
+}
+
 #[account]
 pub struct Puppet {
     pub data: u64,
     pub authority: Pubkey,
+    // NOTE Incremented on every successful data write
+    pub version: u64,
+    // NOTE Set by initialize_puppet_history(). Data writes must then
+    // pass the PuppetHistory so they can't skip the audit trail
+    pub has_history: bool,
+    // NOTE Variable length. Account is realloc'd by set_payload()
+    pub payload: Vec<u8>,
 }
 
 const DISCRIMINATOR_LENGTH: usize = 8; 
 const DATA_LENGTH: usize = 8;
 const AUTHORITY_LENGTH: usize = 32;
+const VERSION_LENGTH: usize = 8;
+const HAS_HISTORY_LENGTH: usize = 1;
+const VEC_PREFIX_LENGTH: usize = 4; // Stores the length of the Vec
 
 impl Puppet {
-    pub const ACCOUNT_SPACE: usize = DISCRIMINATOR_LENGTH + DATA_LENGTH + AUTHORITY_LENGTH;
+    // NOTE Initial space with an empty payload
+    pub const ACCOUNT_SPACE: usize = Self::space(0);
+
+    // NOTE Kept well under the ~1232 byte transaction size limit
+    pub const MAX_PAYLOAD_LENGTH: usize = 512;
+
+    pub const fn space(payload_length: usize) -> usize {
+        DISCRIMINATOR_LENGTH
+            + DATA_LENGTH
+            + AUTHORITY_LENGTH
+            + VERSION_LENGTH
+            + HAS_HISTORY_LENGTH
+            + VEC_PREFIX_LENGTH
+            + payload_length
+    }
+}
+
+#[account]
+#[derive(Default, Debug)]
+pub struct PuppetDelegate {
+    pub puppet: Pubkey,
+    // NOTE Puppet authority that granted this. Stale once it changes
+    pub authority: Pubkey,
+    pub delegate: Pubkey,
+    pub min_data: u64,
+    pub max_data: u64,
+    pub valid_until_slot: Option<u64>,
+    pub bump: u8,
+}
+
+const PUPPET_LENGTH: usize = 32;
+const DELEGATE_LENGTH: usize = 32;
+const MIN_DATA_LENGTH: usize = 8;
+const MAX_DATA_LENGTH: usize = 8;
+const VALID_UNTIL_SLOT_LENGTH: usize = 1 + 8; // Option<u64>
+const BUMP_LENGTH: usize = 1;
+
+impl PuppetDelegate {
+    pub const ACCOUNT_SPACE: usize = DISCRIMINATOR_LENGTH
+        + PUPPET_LENGTH
+        + AUTHORITY_LENGTH
+        + DELEGATE_LENGTH
+        + MIN_DATA_LENGTH
+        + MAX_DATA_LENGTH
+        + VALID_UNTIL_SLOT_LENGTH
+        + BUMP_LENGTH;
+
+    pub const SEED_PREFIX: &'static str = "puppet-delegate";
+}
+
+// NOTE Optional accounts (PuppetDelegate, PuppetHistory) are passed via
+// remaining accounts and picked out by owner + discriminator, so callers
+// can pass either, both, or neither in any order.
+fn find_remaining_account<'a, 'info, T: anchor_lang::Discriminator>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+) -> Option<&'a AccountInfo<'info>> {
+    remaining_accounts
+        .iter()
+        .find(|account_info| is_remaining_account::<T>(account_info))
+}
+
+fn is_remaining_account<T: anchor_lang::Discriminator>(account_info: &AccountInfo) -> bool {
+    account_info.owner == &crate::ID
+        && account_info
+            .try_borrow_data()
+            .map(|data| data.len() >= DISCRIMINATOR_LENGTH && data[..DISCRIMINATOR_LENGTH] == T::discriminator())
+            .unwrap_or(false)
+}
+
+fn authorize_writer(
+    puppet: &Account<Puppet>,
+    signer: &Signer,
+    remaining_accounts: &[AccountInfo],
+    new_data: u64,
+) -> Result<()> {
+    if signer.key() == puppet.authority {
+        return Ok(());
+    }
+
+    let delegate_info = find_remaining_account::<PuppetDelegate>(remaining_accounts)
+        .ok_or(PuppetError::Unauthorized)?;
+    let delegate_record = Account::<PuppetDelegate>::try_from(delegate_info)?;
+    require_keys_eq!(delegate_record.puppet, puppet.key(), PuppetError::Unauthorized);
+    require_keys_eq!(delegate_record.delegate, signer.key(), PuppetError::Unauthorized);
+    require_keys_eq!(delegate_record.authority, puppet.authority, PuppetError::Unauthorized);
+
+    if let Some(valid_until_slot) = delegate_record.valid_until_slot {
+        require_gte!(valid_until_slot, Clock::get()?.slot, PuppetError::DelegateExpired);
+    }
+    require!(
+        (delegate_record.min_data..=delegate_record.max_data).contains(&new_data),
+        PuppetError::DelegateOutOfRange
+    );
+    Ok(())
+}
+
+// NOTE Zero-copy ring buffer header. The HistoryEntry slots are stored
+// right after it in the account data, so capacity can change via realloc.
+// Layout: [discriminator][PuppetHistory][HistoryEntry; capacity]
+#[account(zero_copy)]
+#[derive(Default, Debug)]
+pub struct PuppetHistory {
+    pub puppet: Pubkey,
+    pub capacity: u64,
+    // NOTE Index the next entry is written to
+    pub head: u64,
+    // NOTE Number of recorded entries (<= capacity)
+    pub len: u64,
+    pub bump: u8,
+    pub _padding: [u8; 7],
+}
+
+#[zero_copy]
+#[derive(Default, Debug, Pod, Zeroable)]
+pub struct HistoryEntry {
+    pub slot: u64,
+    pub unix_timestamp: i64,
+    pub setter: Pubkey,
+    pub old_data: u64,
+    pub new_data: u64,
+}
+
+impl PuppetHistory {
+    pub const SEED_PREFIX: &'static str = "puppet-history";
+
+    // NOTE CPI-created accounts are limited to 10KB
+    pub const MAX_CAPACITY: u64 = 128;
+
+    // NOTE Clamped to MAX_CAPACITY. Anchor sizes the init/realloc account
+    // before the is_valid_capacity() constraint runs, so a large capacity
+    // would overflow here (or fail in the System Program) instead of
+    // returning InvalidHistoryCapacity.
+    pub const fn space(capacity: u64) -> usize {
+        let capacity = if capacity > Self::MAX_CAPACITY { Self::MAX_CAPACITY } else { capacity };
+        DISCRIMINATOR_LENGTH + size_of::<PuppetHistory>() + size_of::<HistoryEntry>() * capacity as usize
+    }
+
+    pub fn is_valid_capacity(capacity: u64) -> bool {
+        capacity > 0 && capacity <= Self::MAX_CAPACITY
+    }
+
+    // NOTE Splits raw account data into the header and its entry slots.
+    // AccountLoader::load_mut() only maps the header.
+    pub fn split_mut(data: &mut [u8]) -> Result<(&mut PuppetHistory, &mut [HistoryEntry])> {
+        if data.len() < Self::space(0) {
+            return Err(ErrorCode::AccountDidNotDeserialize.into());
+        }
+        let (header_bytes, entry_bytes) =
+            data[DISCRIMINATOR_LENGTH..].split_at_mut(size_of::<PuppetHistory>());
+        let header: &mut PuppetHistory = bytemuck::from_bytes_mut(header_bytes);
+        let entries_length = size_of::<HistoryEntry>() * header.capacity as usize;
+        if entry_bytes.len() < entries_length {
+            return Err(ErrorCode::AccountDidNotDeserialize.into());
+        }
+        let entries: &mut [HistoryEntry] = bytemuck::cast_slice_mut(&mut entry_bytes[..entries_length]);
+        Ok((header, entries))
+    }
+
+    pub fn push(&mut self, entries: &mut [HistoryEntry], entry: HistoryEntry) {
+        entries[self.head as usize] = entry;
+        self.head = (self.head + 1) % self.capacity;
+        self.len = std::cmp::min(self.len + 1, self.capacity);
+    }
+}
+
+fn record_history(
+    remaining_accounts: &[AccountInfo],
+    puppet: &Account<Puppet>,
+    setter: Pubkey,
+    old_data: u64,
+    new_data: u64,
+) -> Result<()> {
+    let history_info = match find_remaining_account::<PuppetHistory>(remaining_accounts) {
+        Some(history_info) => history_info,
+        None if puppet.has_history => return err!(PuppetError::HistoryRequired),
+        None => return Ok(()),
+    };
+    // NOTE Checks owner and discriminator. load_mut() checks it's writable
+    let history = AccountLoader::<PuppetHistory>::try_from(history_info)?;
+    require_keys_eq!(history.load_mut()?.puppet, puppet.key(), PuppetError::HistoryMismatch);
+
+    let clock = Clock::get()?;
+    let mut data = history_info.try_borrow_mut_data()?;
+    let (header, entries) = PuppetHistory::split_mut(&mut data)?;
+    header.push(
+        entries,
+        HistoryEntry {
+            slot: clock.slot,
+            unix_timestamp: clock.unix_timestamp,
+            setter,
+            old_data,
+            new_data,
+        },
+    );
+    Ok(())
+}
+
+#[error_code]
+pub enum PuppetError {
+    #[msg("Puppet data does not match the expected value")]
+    DataMismatch,
+    #[msg("Math overflow")]
+    MathOverflow,
+    #[msg("Payload exceeds the maximum length")]
+    PayloadTooLarge,
+    #[msg("Bump not found")]
+    BumpNotFound,
+    #[msg("History capacity must be between 1 and PuppetHistory::MAX_CAPACITY")]
+    InvalidHistoryCapacity,
+    #[msg("History account does not belong to this puppet")]
+    HistoryMismatch,
+    #[msg("Signer is neither the puppet authority nor a valid delegate")]
+    Unauthorized,
+    #[msg("Delegate min_data must not exceed max_data")]
+    InvalidDelegateRange,
+    #[msg("Delegation has expired")]
+    DelegateExpired,
+    #[msg("Data is outside the delegate's permitted range")]
+    DelegateOutOfRange,
+    #[msg("Puppet has a history account, which must be passed to record this write")]
+    HistoryRequired,
+    #[msg("Delegate record does not belong to this puppet")]
+    DelegateMismatch,
 }
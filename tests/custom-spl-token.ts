@@ -11,7 +11,7 @@ import {
 } from "@solana/spl-token";
 import { expect } from "chai";
 import { CustomSplToken } from "../target/types/custom_spl_token";
-import { createKeypairFromFile } from "./utils";
+import { createKeypairFromFile, findMetadataPda, TOKEN_METADATA_PROGRAM_ID } from "./utils";
 // import fs from "mz/fs";
 
 // TIL:
@@ -120,13 +120,26 @@ describe("custom-spl-token", () => {
   // console.log("dappTokenManagerV3Pda: ", dappTokenManagerV3Pda);
 
   // Verion 5: Using PDA for BOTH Mint and Mint.authority!
+  // U: Mints are namespaced by (creator, name) now, e.g. one per game season
+  // NOTE Seeded with sha256(name), so any name length derives a PDA
+  const findDappTokenMintPdas = (creator: anchor.web3.PublicKey, name: string) => {
+    const nameHash = Buffer.from(anchor.utils.sha256.hash(name), "hex");
+    const [dappTokenMintPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("dapp-token-mint"), creator.toBuffer(), nameHash],
+      program.programId
+    );
+    const [dappTokenMintRegistryPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("dapp-token-mint-registry"), creator.toBuffer(), nameHash],
+      program.programId
+    );
+    return { dappTokenMintPda, dappTokenMintRegistryPda };
+  };
+  const DAPP_TOKEN_MINT_NAME = "season-1";
   // Get a PDA to use for Mint and its authority
-  const [dappTokenMintPda, dappTokenMintBump] = anchor.web3.PublicKey.findProgramAddressSync(
-    [
-      Buffer.from("dapp-token-mint"),
-    ],
-    program.programId
-  )
+  const { dappTokenMintPda, dappTokenMintRegistryPda } = findDappTokenMintPdas(
+    wallet.publicKey,
+    DAPP_TOKEN_MINT_NAME
+  );
   console.log("dappTokenMintPda: ", dappTokenMintPda);
   let dappTokenMint: Mint;
 
@@ -295,7 +308,12 @@ describe("custom-spl-token", () => {
     // NOTE If I had some unique accounts that can't be inferred,
     // or, if I wanted to use a different payer, then I'd have to pass them in!
     const tx = await program.methods
-      .initializeDappTokenMint()
+      .initializeDappTokenMint(DAPP_TOKEN_MINT_NAME, 9)
+      .accounts({
+        creator: wallet.publicKey,
+        dappTokenMintRegistry: dappTokenMintRegistryPda,
+        mint: dappTokenMintPda,
+      })
       .rpc({ skipPreflight: true }); // Get better logs
     console.log("tx:", tx);
 
@@ -309,6 +327,107 @@ describe("custom-spl-token", () => {
     // expect(dappTokenManagerV3.bump).to.equal(dappTokenManagerV3Bump);
     expect(dappTokenMint.mintAuthority.toBase58()).to.equal(dappTokenMintPda.toBase58());
     expect(dappTokenMint.freezeAuthority.toBase58()).to.equal(dappTokenMintPda.toBase58());
+
+    const dappTokenMintRegistry = await program.account.dappTokenMintRegistry.fetch(dappTokenMintRegistryPda);
+    expect(dappTokenMintRegistry.creator.toBase58()).to.equal(wallet.publicKey.toBase58());
+    expect(dappTokenMintRegistry.mint.toBase58()).to.equal(dappTokenMintPda.toBase58());
+    expect(dappTokenMintRegistry.name).to.equal(DAPP_TOKEN_MINT_NAME);
+    expect(dappTokenMintRegistry.decimals).to.equal(9);
+  });
+
+
+  it("V5: Each (creator, name) namespace gets its own PDA Mint", async () => {
+    const seasonName = "season-2";
+    const { dappTokenMintPda: season2MintPda, dappTokenMintRegistryPda: season2RegistryPda } =
+      findDappTokenMintPdas(user5Wallet.publicKey, seasonName);
+
+    await program.methods
+      .initializeDappTokenMint(seasonName, 6)
+      .accounts({
+        creator: user5Wallet.publicKey,
+        dappTokenMintRegistry: season2RegistryPda,
+        mint: season2MintPda,
+      })
+      .signers([user5Wallet])
+      .rpc();
+
+    const season2Mint = await getMint(provider.connection, season2MintPda);
+    expect(season2Mint.decimals).to.equal(6);
+    expect(season2Mint.mintAuthority.toBase58()).to.equal(season2MintPda.toBase58());
+    expect(season2MintPda.toBase58()).to.not.equal(dappTokenMintPda.toBase58());
+    const season2Registry = await program.account.dappTokenMintRegistry.fetch(season2RegistryPda);
+    expect(season2Registry.creator.toBase58()).to.equal(user5Wallet.publicKey.toBase58());
+    expect(season2Registry.decimals).to.equal(6);
+
+    // Minting through the wrong registry is rejected by the mint's PDA seeds
+    try {
+      await program.methods
+        .mintDappTokenSupply(new anchor.BN(MINT_AMOUNT_RAW))
+        .accounts({
+          userTokenAccount: getAssociatedTokenAddressSync(season2MintPda, user5Wallet.publicKey),
+          mint: season2MintPda,
+          dappTokenMintRegistry: dappTokenMintRegistryPda,
+          creator: wallet.publicKey,
+          user: user5Wallet.publicKey,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      expect.fail("Mint with a mismatched registry should have been rejected");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("ConstraintSeeds");
+    }
+
+    // Only a namespace's creator can mint its supply
+    const season2MintAccounts = {
+      userTokenAccount: getAssociatedTokenAddressSync(season2MintPda, wallet.publicKey),
+      mint: season2MintPda,
+      dappTokenMintRegistry: season2RegistryPda,
+      user: wallet.publicKey,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+    try {
+      await program.methods
+        .mintDappTokenSupply(new anchor.BN(MINT_AMOUNT_RAW))
+        .accounts({ ...season2MintAccounts, creator: wallet.publicKey })
+        .rpc();
+      expect.fail("Mint by a non-creator should have been rejected");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("Unauthorized");
+    }
+    expect((await getMint(provider.connection, season2MintPda)).supply.toString()).to.equal("0");
+
+    await program.methods
+      .mintDappTokenSupply(new anchor.BN(MINT_AMOUNT_RAW))
+      .accounts({ ...season2MintAccounts, creator: user5Wallet.publicKey })
+      .signers([user5Wallet])
+      .rpc();
+    expect((await getMint(provider.connection, season2MintPda)).supply.toString())
+      .to.equal(MINT_AMOUNT_RAW.toString());
+  });
+
+
+  it("V5: Reject an empty or too-long Mint name with InvalidMintName", async () => {
+    for (const badName of ["", "x".repeat(33)]) {
+      const { dappTokenMintPda: badMintPda, dappTokenMintRegistryPda: badRegistryPda } =
+        findDappTokenMintPdas(wallet.publicKey, badName);
+      try {
+        await program.methods
+          .initializeDappTokenMint(badName, 9)
+          .accounts({
+            creator: wallet.publicKey,
+            dappTokenMintRegistry: badRegistryPda,
+            mint: badMintPda,
+          })
+          .rpc();
+        expect.fail("Invalid mint name should have been rejected");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidMintName");
+      }
+      // Nothing was created
+      expect(await provider.connection.getAccountInfo(badRegistryPda)).to.be.null;
+    }
   });
 
 
@@ -332,6 +451,8 @@ describe("custom-spl-token", () => {
         .accounts({
           userTokenAccount: user5TokenAccount,
           mint: dappTokenMintPda,
+          dappTokenMintRegistry: dappTokenMintRegistryPda,
+          creator: wallet.publicKey,
           user: user5Wallet.publicKey,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
           tokenProgram: TOKEN_PROGRAM_ID,
@@ -342,7 +463,8 @@ describe("custom-spl-token", () => {
         // A: Wait! Why pass mintKeypair!? I need ATA, which is
         // getting init_if_needed in program and payer = user, 
         // so I just need USER WALLET!
-        .signers([user5Wallet])
+        // U: Only the registry's creator (wallet) can mint now, and it
+        // pays for the ATA, so no extra signers.
         .rpc({ skipPreflight: true }); // Get better logs
       console.log("TxHash ::", tx);
     } catch (err: any) {
@@ -395,6 +517,484 @@ describe("custom-spl-token", () => {
   });
 
 
+  it("Reject minting zero dappTokenMint supply with ZeroAmount error", async () => {
+    try {
+      await program.methods
+        .mintDappTokenSupply(new anchor.BN(0))
+        .accounts({
+          userTokenAccount: user5TokenAccount,
+          mint: dappTokenMintPda,
+          dappTokenMintRegistry: dappTokenMintRegistryPda,
+          creator: wallet.publicKey,
+          user: user5Wallet.publicKey,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      expect.fail("Mint should have been rejected");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("ZeroAmount");
+      expect(err.error.errorCode.number).to.equal(6000);
+    }
+  });
+
+
+  it("V1: mintDappSpl requires the DTM authority or the token account owner to sign", async () => {
+    const mintKeypairV1 = anchor.web3.Keypair.generate();
+    const [dappTokenManagerV1Pda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("dapp-token-manager-v1"),
+        mintKeypairV1.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+    const strangerWallet = anchor.web3.Keypair.generate();
+
+    await program.methods
+      .initializeDappSplWithKeypair()
+      .accounts({
+        mint: mintKeypairV1.publicKey,
+        dappTokenManagerV1: dappTokenManagerV1Pda,
+        authority: wallet.publicKey,
+      })
+      .signers([mintKeypairV1])
+      .rpc();
+
+    // NOTE V1 expects the ATA to be created from the CLIENT
+    const user5TokenAccountV1 = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      wallet.payer,
+      mintKeypairV1.publicKey,
+      user5Wallet.publicKey
+    );
+
+    const mintDappSplAccounts = {
+      mint: mintKeypairV1.publicKey,
+      dappTokenManagerV1: dappTokenManagerV1Pda,
+      userTokenAccount: user5TokenAccountV1.address,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+
+    // Unsigned: the minter's signature is missing from the transaction
+    try {
+      await program.methods
+        .mintDappSpl()
+        .accounts({ ...mintDappSplAccounts, minter: user5Wallet.publicKey })
+        .rpc();
+      expect.fail("Unsigned mintDappSpl should have been rejected");
+    } catch (err: any) {
+      expect(err.message).to.match(/signature/i);
+    }
+
+    // Signed, but neither the DTM authority nor the token account owner
+    try {
+      await program.methods
+        .mintDappSpl()
+        .accounts({ ...mintDappSplAccounts, minter: strangerWallet.publicKey })
+        .signers([strangerWallet])
+        .rpc();
+      expect.fail("Stranger mintDappSpl should have been rejected");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("Unauthorized");
+    }
+
+    let user5TokenAccountV1Info = await getAccount(provider.connection, user5TokenAccountV1.address);
+    expect(user5TokenAccountV1Info.amount.toString()).to.equal("0");
+
+    // Owner signing for their own token account
+    await program.methods
+      .mintDappSpl()
+      .accounts({ ...mintDappSplAccounts, minter: user5Wallet.publicKey })
+      .signers([user5Wallet])
+      .rpc();
+
+    // DTM authority (wallet) minting to the user's token account
+    await program.methods
+      .mintDappSpl()
+      .accounts({ ...mintDappSplAccounts, minter: wallet.publicKey })
+      .rpc();
+
+    user5TokenAccountV1Info = await getAccount(provider.connection, user5TokenAccountV1.address);
+    expect(user5TokenAccountV1Info.amount.toString()).to.equal((MINT_AMOUNT_RAW * 2).toString());
+    expect((await program.account.dappTokenManagerV1.fetch(dappTokenManagerV1Pda))
+      .totalUserMintCount.toNumber()).to.equal(2);
+  });
+
+
+  it("V2: Initialize singleton DappTokenManagerV2, adopt a Mint, then mint supply", async () => {
+    const mintKeypairV2 = anchor.web3.Keypair.generate();
+    const [dappTokenManagerV2Pda, dappTokenManagerV2Bump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("dapp-token-manager-v2")],
+      program.programId
+    );
+
+    await program.methods
+      .initializeDappTokenManagerV2()
+      .accounts({
+        dappTokenManagerV2: dappTokenManagerV2Pda,
+        authority: wallet.publicKey,
+      })
+      .rpc();
+
+    // Regression: new(mint, authority, ..) used to get (authority, token_mint)
+    let dappTokenManagerV2 = await program.account.dappTokenManagerV2.fetch(dappTokenManagerV2Pda);
+    expect(dappTokenManagerV2.authority.toBase58()).to.equal(wallet.publicKey.toBase58());
+    expect(dappTokenManagerV2.mint.toBase58()).to.equal(anchor.web3.PublicKey.default.toBase58());
+    expect(dappTokenManagerV2.bump).to.equal(dappTokenManagerV2Bump);
+
+    await program.methods
+      .initializeDappTokenMintV2()
+      .accounts({
+        mint: mintKeypairV2.publicKey,
+        dappTokenManagerV2: dappTokenManagerV2Pda,
+        authority: wallet.publicKey,
+      })
+      .signers([mintKeypairV2])
+      .rpc();
+
+    dappTokenManagerV2 = await program.account.dappTokenManagerV2.fetch(dappTokenManagerV2Pda);
+    expect(dappTokenManagerV2.mint.toBase58()).to.equal(mintKeypairV2.publicKey.toBase58());
+    const dappTokenMintV2 = await getMint(provider.connection, mintKeypairV2.publicKey);
+    expect(dappTokenMintV2.mintAuthority.toBase58()).to.equal(dappTokenManagerV2Pda.toBase58());
+    expect(dappTokenMintV2.freezeAuthority.toBase58()).to.equal(dappTokenManagerV2Pda.toBase58());
+
+    // A mint can only be adopted once
+    const otherMintKeypair = anchor.web3.Keypair.generate();
+    try {
+      await program.methods
+        .initializeDappTokenMintV2()
+        .accounts({
+          mint: otherMintKeypair.publicKey,
+          dappTokenManagerV2: dappTokenManagerV2Pda,
+          authority: wallet.publicKey,
+        })
+        .signers([otherMintKeypair])
+        .rpc();
+      expect.fail("Second initializeDappTokenMintV2 should have been rejected");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("MintAlreadyAdopted");
+    }
+
+    const user5TokenAccountV2 = getAssociatedTokenAddressSync(
+      mintKeypairV2.publicKey,
+      user5Wallet.publicKey,
+    );
+    await program.methods
+      .mintDappTokenSupplyV2()
+      .accounts({
+        userTokenAccount: user5TokenAccountV2,
+        mint: mintKeypairV2.publicKey,
+        dappTokenManagerV2: dappTokenManagerV2Pda,
+        user: user5Wallet.publicKey,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user5Wallet])
+      .rpc();
+
+    const user5TokenAccountV2Info = await getAccount(provider.connection, user5TokenAccountV2);
+    expect(user5TokenAccountV2Info.amount.toString()).to.equal(MINT_AMOUNT_RAW.toString());
+    dappTokenManagerV2 = await program.account.dappTokenManagerV2.fetch(dappTokenManagerV2Pda);
+    expect(dappTokenManagerV2.totalUserMintCount.toNumber()).to.equal(1);
+  });
+
+
+  it("V3: Initialize DappTokenManagerV3 + Mint with token metadata, then update it", async () => {
+    const mintKeypairV3Metadata = anchor.web3.Keypair.generate();
+    const [dappTokenManagerV3MetadataPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("dapp-token-manager-v3"),
+        mintKeypairV3Metadata.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+    const metadataPda = findMetadataPda(mintKeypairV3Metadata.publicKey);
+
+    await program.methods
+      .initializeDappTokenManagerAndTokenMintV3({
+        name: "Dapp Token V3",
+        symbol: "DAPP3",
+        uri: "https://example.com/dapp3.json",
+      })
+      .accounts({
+        mint: mintKeypairV3Metadata.publicKey,
+        dappTokenManagerV3: dappTokenManagerV3MetadataPda,
+        authority: wallet.publicKey,
+        metadata: metadataPda,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      })
+      .signers([mintKeypairV3Metadata])
+      .rpc();
+
+    let metadataInfo = await provider.connection.getAccountInfo(metadataPda);
+    expect(metadataInfo.owner.toBase58()).to.equal(TOKEN_METADATA_PROGRAM_ID.toBase58());
+    expect(metadataInfo.data.includes(Buffer.from("DAPP3"))).to.equal(true);
+
+    await program.methods
+      .updateDappTokenMetadataV3({
+        name: "Dapp Token V3 S2",
+        symbol: "DAPP3S2",
+        uri: "https://example.com/dapp3s2.json",
+      })
+      .accounts({
+        dappTokenManagerV3: dappTokenManagerV3MetadataPda,
+        metadata: metadataPda,
+        authority: wallet.publicKey,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      })
+      .rpc();
+
+    metadataInfo = await provider.connection.getAccountInfo(metadataPda);
+    expect(metadataInfo.data.includes(Buffer.from("DAPP3S2"))).to.equal(true);
+  });
+
+
+  it("Unified: Initialize DappTokenManager + Mint, then mint supply", async () => {
+    const mintKeypair = anchor.web3.Keypair.generate();
+    const [dappTokenManagerPda, dappTokenManagerBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("dapp-token-manager"),
+        mintKeypair.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+
+    const initializeManagerAccounts = {
+      mint: mintKeypair.publicKey,
+      dappTokenManager: dappTokenManagerPda,
+      authority: wallet.publicKey,
+      metadata: null, // optional, no token metadata
+      tokenMetadataProgram: null,
+    };
+
+    // Token metadata needs the optional metadata accounts
+    try {
+      await program.methods
+        .initializeManager(
+          { name: "Dapp Token", symbol: "DAPP", uri: "https://example.com/dapp.json" },
+          null
+        )
+        .accounts(initializeManagerAccounts)
+        .signers([mintKeypair])
+        .rpc();
+      expect.fail("initializeManager with metadata but no metadata accounts should have been rejected");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("MetadataAccountsRequired");
+    }
+
+    await program.methods
+      .initializeManager(null, null)
+      .accounts(initializeManagerAccounts)
+      .signers([mintKeypair])
+      .rpc();
+
+    let dappTokenManager = await program.account.dappTokenManager.fetch(dappTokenManagerPda);
+    expect(dappTokenManager.version).to.equal(1);
+    expect(dappTokenManager.migratedFrom).to.equal(0);
+    expect(dappTokenManager.mint.toBase58()).to.equal(mintKeypair.publicKey.toBase58());
+    expect(dappTokenManager.authority.toBase58()).to.equal(wallet.publicKey.toBase58());
+    expect(dappTokenManager.bump).to.equal(dappTokenManagerBump);
+    const dappTokenMint = await getMint(provider.connection, mintKeypair.publicKey);
+    expect(dappTokenMint.mintAuthority.toBase58()).to.equal(dappTokenManagerPda.toBase58());
+    expect(dappTokenMint.freezeAuthority.toBase58()).to.equal(dappTokenManagerPda.toBase58());
+
+    const user5TokenAccount = getAssociatedTokenAddressSync(
+      mintKeypair.publicKey,
+      user5Wallet.publicKey,
+    );
+    const strangerWallet = anchor.web3.Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(
+        strangerWallet.publicKey,
+        anchor.web3.LAMPORTS_PER_SOL
+      )
+    );
+    const mintManagerSupplyAccounts = {
+      userTokenAccount: user5TokenAccount,
+      mint: mintKeypair.publicKey,
+      dappTokenManager: dappTokenManagerPda,
+      user: user5Wallet.publicKey,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+
+    // Unsigned: the minter's signature is missing from the transaction
+    try {
+      await program.methods
+        .mintManagerSupply()
+        .accounts({ ...mintManagerSupplyAccounts, minter: user5Wallet.publicKey })
+        .rpc();
+      expect.fail("Unsigned mintManagerSupply should have been rejected");
+    } catch (err: any) {
+      expect(err.message).to.match(/signature/i);
+    }
+
+    // Signed, but neither the DTM authority nor the token account owner
+    try {
+      await program.methods
+        .mintManagerSupply()
+        .accounts({ ...mintManagerSupplyAccounts, minter: strangerWallet.publicKey })
+        .signers([strangerWallet])
+        .rpc();
+      expect.fail("Stranger mintManagerSupply should have been rejected");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("Unauthorized");
+    }
+    expect(await provider.connection.getAccountInfo(user5TokenAccount)).to.be.null;
+
+    // Owner signing for their own ATA
+    await program.methods
+      .mintManagerSupply()
+      .accounts({ ...mintManagerSupplyAccounts, minter: user5Wallet.publicKey })
+      .signers([user5Wallet])
+      .rpc();
+
+    // DTM authority (wallet) minting to the user's ATA
+    await program.methods
+      .mintManagerSupply()
+      .accounts({ ...mintManagerSupplyAccounts, minter: wallet.publicKey })
+      .rpc();
+
+    const user5TokenAccountInfo = await getAccount(provider.connection, user5TokenAccount);
+    expect(user5TokenAccountInfo.amount.toString()).to.equal((MINT_AMOUNT_RAW * 2).toString());
+    dappTokenManager = await program.account.dappTokenManager.fetch(dappTokenManagerPda);
+    expect(dappTokenManager.totalUserMintCount.toNumber()).to.equal(2);
+  });
+
+
+  it("Unified: migrateManager moves V1, V2 and V3 mint/freeze authority to DappTokenManager", async () => {
+    for (const legacyVersion of [1, 2, 3]) {
+      const mintKeypair = anchor.web3.Keypair.generate();
+      let [legacyPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from(`dapp-token-manager-v${legacyVersion}`),
+          mintKeypair.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+      // NOTE V2 is a singleton (no mint seed) that already adopted a mint in the V2 test
+      let mint = mintKeypair.publicKey;
+      if (legacyVersion === 2) {
+        [legacyPda] = anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("dapp-token-manager-v2")],
+          program.programId
+        );
+        mint = (await program.account.dappTokenManagerV2.fetch(legacyPda)).mint;
+      }
+      const [dappTokenManagerPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("dapp-token-manager"),
+          mint.toBuffer(),
+        ],
+        program.programId
+      );
+
+      if (legacyVersion === 1) {
+        await program.methods
+          .initializeDappSplWithKeypair()
+          .accounts({
+            mint: mintKeypair.publicKey,
+            dappTokenManagerV1: legacyPda,
+            authority: wallet.publicKey,
+          })
+          .signers([mintKeypair])
+          .rpc();
+      } else if (legacyVersion === 3) {
+        await program.methods
+          .initializeDappTokenManagerAndTokenMintV3(null)
+          .accounts({
+            mint: mintKeypair.publicKey,
+            dappTokenManagerV3: legacyPda,
+            authority: wallet.publicKey,
+            metadata: findMetadataPda(mintKeypair.publicKey),
+            tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+          })
+          .signers([mintKeypair])
+          .rpc();
+      }
+
+      const user5TokenAccount = getAssociatedTokenAddressSync(
+        mint,
+        user5Wallet.publicKey,
+      );
+      const mintManagerSupplyAccounts = {
+        userTokenAccount: user5TokenAccount,
+        mint,
+        dappTokenManager: dappTokenManagerPda,
+        user: user5Wallet.publicKey,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      };
+
+      // Only the legacy DTM's authority can migrate
+      try {
+        await program.methods
+          .migrateManager()
+          .accounts({
+            mint,
+            legacyDappTokenManager: legacyPda,
+            dappTokenManager: dappTokenManagerPda,
+            authority: user5Wallet.publicKey,
+          })
+          .signers([user5Wallet])
+          .rpc();
+        expect.fail("migrateManager by a non-authority should have been rejected");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("Unauthorized");
+      }
+
+      await program.methods
+        .migrateManager()
+        .accounts({
+          mint,
+          legacyDappTokenManager: legacyPda,
+          dappTokenManager: dappTokenManagerPda,
+          authority: wallet.publicKey,
+        })
+        .rpc();
+
+      const dappTokenManager = await program.account.dappTokenManager.fetch(dappTokenManagerPda);
+      expect(dappTokenManager.version).to.equal(1);
+      expect(dappTokenManager.migratedFrom).to.equal(legacyVersion);
+      expect(dappTokenManager.mint.toBase58()).to.equal(mint.toBase58());
+      expect(dappTokenManager.authority.toBase58()).to.equal(wallet.publicKey.toBase58());
+      const dappTokenMint = await getMint(provider.connection, mint);
+      expect(dappTokenMint.mintAuthority.toBase58()).to.equal(dappTokenManagerPda.toBase58());
+      expect(dappTokenMint.freezeAuthority.toBase58()).to.equal(dappTokenManagerPda.toBase58());
+
+      // The already-deployed mint keeps working through the unified handler
+      const balanceBefore = legacyVersion === 2
+        ? (await getAccount(provider.connection, user5TokenAccount)).amount
+        : BigInt(0);
+      await program.methods
+        .mintManagerSupply()
+        .accounts({ ...mintManagerSupplyAccounts, minter: user5Wallet.publicKey })
+        .signers([user5Wallet])
+        .rpc();
+      const user5TokenAccountInfo = await getAccount(provider.connection, user5TokenAccount);
+      expect(user5TokenAccountInfo.amount.toString()).to.equal(
+        (balanceBefore + BigInt(MINT_AMOUNT_RAW)).toString()
+      );
+
+      // Migrating twice fails since the unified PDA already exists
+      try {
+        await program.methods
+          .migrateManager()
+          .accounts({
+            mint,
+            legacyDappTokenManager: legacyPda,
+            dappTokenManager: dappTokenManagerPda,
+            authority: wallet.publicKey,
+          })
+          .rpc();
+        expect.fail("Second migrateManager should have been rejected");
+      } catch (err: any) {
+        expect((err.logs ?? []).join("\n")).to.match(/already in use/i);
+      }
+    }
+  });
+
+
   // xit("AGAIN, Mint dappTokenMintV3 supply to user5TokenAccount (ATA already created)", async () => {
   //   // NOTE Using init_if_needed in validation struct for user_token_account.
   //   // Q: Do I still need to getAssociatedTokenAddressSync()?
@@ -1,18 +1,22 @@
 import * as anchor from "@project-serum/anchor";
 import {
   TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
   ASSOCIATED_TOKEN_PROGRAM_ID,
   createAssociatedTokenAccountInstruction,
   getAccount,
   getAssociatedTokenAddressSync,
   getMint,
   getOrCreateAssociatedTokenAccount,
+  getInterestBearingMintConfigState,
+  getNonTransferable,
+  getTransferFeeConfig,
   Mint,
 } from "@solana/spl-token";
 import { expect } from "chai";
 import { DappTokenManagerProgram } from "../target/types/dapp_token_manager_program";
 import { MasterProgram } from "../target/types/master_program";
-import { createKeypairFromFile } from "./utils";
+import { createKeypairFromFile, findMetadataPda, TOKEN_METADATA_PROGRAM_ID } from "./utils";
 
 // TIL:
 // - PDAs can only sign from within program context!
@@ -56,6 +60,10 @@ import { createKeypairFromFile } from "./utils";
 const ONE_TOKEN_AMOUNT_RAW = 1000000000;
 const MINT_AMOUNT_RAW = ONE_TOKEN_AMOUNT_RAW * 100; // 100 full tokens
 const MINT_AMOUNT_UI = 100; // 100 full tokens
+const DECIMALS = 9;
+const MAX_SUPPLY_RAW = MINT_AMOUNT_RAW * 10; // 1000 full tokens
+const COOLDOWN_SECONDS = 0; // No wait between mints (keeps tests fast)
+const MAX_MINTS_PER_USER = 2;
 
 
 describe("dapp-token-manager-program", () => {
@@ -102,12 +110,12 @@ describe("dapp-token-manager-program", () => {
   // NOTE If Caller wants 'authority' to be KEYPAIR, then need to generate.
   const authorityKeypair = anchor.web3.Keypair.generate();
 
-  // Derive the DTM PDA using caller program and caller
+  // Derive the DTM PDA using the mint
+  // U: 'authority' is no longer a seed so it can be rotated
   const [dappTokenManagerPda, dappTokenManagerBump] = anchor.utils.publicKey.findProgramAddressSync(
     [
       Buffer.from("dapp-token-manager"),
       dappTokenMintPubkey.toBuffer(),
-      authorityPda.toBuffer(), // <-- PDA
     ],
     dappTokenManagerProgram.programId
   );
@@ -118,6 +126,16 @@ describe("dapp-token-manager-program", () => {
   console.log("user1Wallet: ", user1Wallet.publicKey.toBase58())
   let user1TokenAccount: anchor.web3.PublicKey;
 
+  // Per-user mint record PDA (DTM + user)
+  const [user1MintRecordPda] = anchor.utils.publicKey.findProgramAddressSync(
+    [
+      Buffer.from("user-mint-record"),
+      dappTokenManagerPda.toBuffer(),
+      user1Wallet.publicKey.toBuffer(),
+    ],
+    dappTokenManagerProgram.programId
+  );
+
   before(async () => {
     await provider.connection.confirmTransaction(
       await provider.connection.requestAirdrop(
@@ -159,14 +177,22 @@ describe("dapp-token-manager-program", () => {
     // then I need to derive it from masterProgram. If 'authority' is a
     // Keypair, then I need to pass it instead
     const tx = await dappTokenManagerProgram.methods
-      .createDappTokenManager(
-        authorityPda,
-        new anchor.BN(MINT_AMOUNT_RAW)
-      )
+      .createDappTokenManager({
+        authority: authorityPda,
+        supplyAmountPerMint: new anchor.BN(MINT_AMOUNT_RAW),
+        decimals: DECIMALS,
+        maxSupply: new anchor.BN(MAX_SUPPLY_RAW),
+        cooldownSeconds: new anchor.BN(COOLDOWN_SECONDS),
+        maxMintsPerUser: new anchor.BN(MAX_MINTS_PER_USER),
+        tokenMetadata: null, // no token metadata
+        tokenExtensions: null, // no token extensions (legacy Token program)
+      })
       .accounts({
         mint: dappTokenMintKeypair.publicKey,
         dappTokenManager: dappTokenManagerPda,
         authorityPayer: authorityPayer.publicKey,
+        metadata: null, // optional, no token metadata
+        tokenMetadataProgram: null,
       })
       // NOTE I was right that the dappTokenMintPersistKeypair and wallet are signers,
       // but you don't pass wallet as signer for Anchor. It already knows.
@@ -187,8 +213,16 @@ describe("dapp-token-manager-program", () => {
     console.log("dappTokenMint: ", dappTokenMint);
 
     expect(dappTokenManager.mint.toBase58()).to.equal(dappTokenMintKeypair.publicKey.toBase58());
+    expect(dappTokenManager.tokenProgram.toBase58()).to.equal(TOKEN_PROGRAM_ID.toBase58());
+    expect(dappTokenManager.authority.toBase58()).to.equal(authorityPda.toBase58());
+    expect(dappTokenManager.pendingAuthority.toBase58()).to.equal(anchor.web3.PublicKey.default.toBase58());
     expect(dappTokenManager.totalMintCount.toNumber()).to.equal(0);
+    expect(dappTokenManager.decimals).to.equal(DECIMALS);
+    expect(dappTokenManager.maxSupply.toString()).to.equal(MAX_SUPPLY_RAW.toString());
+    expect(dappTokenManager.cooldownSeconds.toNumber()).to.equal(COOLDOWN_SECONDS);
+    expect(dappTokenManager.maxMintsPerUser.toNumber()).to.equal(MAX_MINTS_PER_USER);
     expect(dappTokenManager.bump).to.equal(dappTokenManagerBump);
+    expect(dappTokenMint.decimals).to.equal(DECIMALS);
     expect(dappTokenMint.mintAuthority.toBase58()).to.equal(dappTokenManagerPda.toBase58());
     expect(dappTokenMint.freezeAuthority.toBase58()).to.equal(dappTokenManagerPda.toBase58());
   });
@@ -219,6 +253,7 @@ describe("dapp-token-manager-program", () => {
           userTokenAccount: user1TokenAccount,
           mint: dappTokenMintKeypair.publicKey,
           dappTokenManager: dappTokenManagerPda,
+          userMintRecord: user1MintRecordPda,
           user: user1Wallet.publicKey,
           // authority: authorityPda, // U: Not sure I need this.
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -312,6 +347,7 @@ describe("dapp-token-manager-program", () => {
           userTokenAccount: user1TokenAccount,
           mint: dappTokenMintKeypair.publicKey,
           dappTokenManager: dappTokenManagerPda,
+          userMintRecord: user1MintRecordPda,
           user: user1Wallet.publicKey,
           // authority: authorityPda, // U: Not sure I need this.
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -376,8 +412,919 @@ describe("dapp-token-manager-program", () => {
     expect((currentUser1TokenAccountInfo.amount / BigInt(ONE_TOKEN_AMOUNT_RAW)).toString()).to.equal((MINT_AMOUNT_UI * 2).toString());
     // - user1TokenAccount.owner should be user1Wallet.pubkey
     expect(currentUser1TokenAccountInfo.owner.toBase58()).to.equal(user1Wallet.publicKey.toBase58())
+    // - user1MintRecord tracks both mints
+    const user1MintRecord = await dappTokenManagerProgram.account.userMintRecord.fetch(user1MintRecordPda);
+    expect(user1MintRecord.user.toBase58()).to.equal(user1Wallet.publicKey.toBase58());
+    expect(user1MintRecord.totalMintCount.toNumber()).to.equal(2);
+    expect(user1MintRecord.totalMintedAmount.toString()).to.equal((MINT_AMOUNT_RAW * 2).toString());
+  });
+
+  it("Reject mint once user1 reaches maxMintsPerUser", async () => {
+    try {
+      await dappTokenManagerProgram.methods
+        .mintDappTokenSupply()
+        .accounts({
+          userTokenAccount: user1TokenAccount,
+          mint: dappTokenMintKeypair.publicKey,
+          dappTokenManager: dappTokenManagerPda,
+          userMintRecord: user1MintRecordPda,
+          user: user1Wallet.publicKey,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user1Wallet])
+        .rpc();
+      expect.fail("Mint should have been rejected");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("UserMintLimitReached");
+    }
+
+    dappTokenManager = await dappTokenManagerProgram.account.dappTokenManager.fetch(dappTokenManagerPda);
+    expect(dappTokenManager.totalMintCount.toNumber()).to.equal(MAX_MINTS_PER_USER);
+  });
+
+  it("Reject a second mint before cooldownSeconds elapse, and a negative cooldown", async () => {
+    const COOLDOWN_TEST_SECONDS = 60 * 60; // 1 hour, never elapses during the test
+    const cooldownMintKeypair = anchor.web3.Keypair.generate();
+    const [cooldownDtmPda] = anchor.utils.publicKey.findProgramAddressSync(
+      [Buffer.from("dapp-token-manager"), cooldownMintKeypair.publicKey.toBuffer()],
+      dappTokenManagerProgram.programId
+    );
+    const [user1CooldownMintRecordPda] = anchor.utils.publicKey.findProgramAddressSync(
+      [Buffer.from("user-mint-record"), cooldownDtmPda.toBuffer(), user1Wallet.publicKey.toBuffer()],
+      dappTokenManagerProgram.programId
+    );
+    const createArgs = {
+      authority: authorityKeypair.publicKey,
+      supplyAmountPerMint: new anchor.BN(MINT_AMOUNT_RAW),
+      decimals: DECIMALS,
+      maxSupply: new anchor.BN(0), // unlimited
+      cooldownSeconds: new anchor.BN(COOLDOWN_TEST_SECONDS),
+      maxMintsPerUser: new anchor.BN(0), // unlimited
+      tokenMetadata: null,
+      tokenExtensions: null,
+    };
+    const createAccounts = {
+      mint: cooldownMintKeypair.publicKey,
+      dappTokenManager: cooldownDtmPda,
+      authorityPayer: authorityPayer.publicKey,
+      metadata: null, // optional, no token metadata
+      tokenMetadataProgram: null,
+    };
+
+    try {
+      await dappTokenManagerProgram.methods
+        .createDappTokenManager({ ...createArgs, cooldownSeconds: new anchor.BN(-1) })
+        .accounts(createAccounts)
+        .signers([cooldownMintKeypair, authorityPayer])
+        .rpc();
+      expect.fail("A negative cooldownSeconds should have been rejected");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InvalidCooldown");
+    }
+
+    await dappTokenManagerProgram.methods
+      .createDappTokenManager(createArgs)
+      .accounts(createAccounts)
+      .signers([cooldownMintKeypair, authorityPayer])
+      .rpc();
+
+    const cooldownMintAccounts = {
+      userTokenAccount: getAssociatedTokenAddressSync(cooldownMintKeypair.publicKey, user1Wallet.publicKey),
+      mint: cooldownMintKeypair.publicKey,
+      dappTokenManager: cooldownDtmPda,
+      userMintRecord: user1CooldownMintRecordPda,
+      user: user1Wallet.publicKey,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+    // First mint has no previous mint to wait on
+    await dappTokenManagerProgram.methods
+      .mintDappTokenSupply()
+      .accounts(cooldownMintAccounts)
+      .signers([user1Wallet])
+      .rpc();
+
+    try {
+      await dappTokenManagerProgram.methods
+        .mintDappTokenSupply()
+        .accounts(cooldownMintAccounts)
+        .signers([user1Wallet])
+        .rpc();
+      expect.fail("Mint should have been rejected during the cooldown");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("MintCooldownNotElapsed");
+    }
+
+    const user1CooldownMintRecord = await dappTokenManagerProgram.account.userMintRecord.fetch(
+      user1CooldownMintRecordPda
+    );
+    expect(user1CooldownMintRecord.totalMintCount.toNumber()).to.equal(1);
+  });
+
+  it("Reject a mint past maxSupply without partially applying it", async () => {
+    const CAPPED_MAX_SUPPLY_RAW = MINT_AMOUNT_RAW * 1.5; // room for one mint only
+    const cappedMintKeypair = anchor.web3.Keypair.generate();
+    const [cappedDtmPda] = anchor.utils.publicKey.findProgramAddressSync(
+      [Buffer.from("dapp-token-manager"), cappedMintKeypair.publicKey.toBuffer()],
+      dappTokenManagerProgram.programId
+    );
+    const [user1CappedMintRecordPda] = anchor.utils.publicKey.findProgramAddressSync(
+      [Buffer.from("user-mint-record"), cappedDtmPda.toBuffer(), user1Wallet.publicKey.toBuffer()],
+      dappTokenManagerProgram.programId
+    );
+    const user1CappedTokenAccount = getAssociatedTokenAddressSync(
+      cappedMintKeypair.publicKey,
+      user1Wallet.publicKey
+    );
+
+    await dappTokenManagerProgram.methods
+      .createDappTokenManager({
+        authority: authorityKeypair.publicKey,
+        supplyAmountPerMint: new anchor.BN(MINT_AMOUNT_RAW),
+        decimals: DECIMALS,
+        maxSupply: new anchor.BN(CAPPED_MAX_SUPPLY_RAW),
+        cooldownSeconds: new anchor.BN(0),
+        maxMintsPerUser: new anchor.BN(0), // unlimited, so only the cap applies
+        tokenMetadata: null,
+        tokenExtensions: null,
+      })
+      .accounts({
+        mint: cappedMintKeypair.publicKey,
+        dappTokenManager: cappedDtmPda,
+        authorityPayer: authorityPayer.publicKey,
+        metadata: null, // optional, no token metadata
+        tokenMetadataProgram: null,
+      })
+      .signers([cappedMintKeypair, authorityPayer])
+      .rpc();
+
+    const cappedMintAccounts = {
+      userTokenAccount: user1CappedTokenAccount,
+      mint: cappedMintKeypair.publicKey,
+      dappTokenManager: cappedDtmPda,
+      userMintRecord: user1CappedMintRecordPda,
+      user: user1Wallet.publicKey,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+    await dappTokenManagerProgram.methods
+      .mintDappTokenSupply()
+      .accounts(cappedMintAccounts)
+      .signers([user1Wallet])
+      .rpc();
+
+    try {
+      await dappTokenManagerProgram.methods
+        .mintDappTokenSupply()
+        .accounts(cappedMintAccounts)
+        .signers([user1Wallet])
+        .rpc();
+      expect.fail("Mint past maxSupply should have been rejected");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("SupplyCapExceeded");
+    }
+
+    // Nothing from the rejected mint was applied
+    const cappedMint = await getMint(provider.connection, cappedMintKeypair.publicKey);
+    expect(cappedMint.supply.toString()).to.equal(MINT_AMOUNT_RAW.toString());
+    const user1CappedTokenAccountInfo = await getAccount(provider.connection, user1CappedTokenAccount);
+    expect(user1CappedTokenAccountInfo.amount.toString()).to.equal(MINT_AMOUNT_RAW.toString());
+    const cappedDtm = await dappTokenManagerProgram.account.dappTokenManager.fetch(cappedDtmPda);
+    expect(cappedDtm.totalMintCount.toNumber()).to.equal(1);
+    expect(cappedDtm.circulatingSupply.toString()).to.equal(MINT_AMOUNT_RAW.toString());
+    const user1CappedMintRecord = await dappTokenManagerProgram.account.userMintRecord.fetch(
+      user1CappedMintRecordPda
+    );
+    expect(user1CappedMintRecord.totalMintCount.toNumber()).to.equal(1);
+    expect(user1CappedMintRecord.totalMintedAmount.toString()).to.equal(MINT_AMOUNT_RAW.toString());
+  });
+
+  it("Burn dappTokenMint supply from user1TokenAccount", async () => {
+    const BURN_AMOUNT_RAW = ONE_TOKEN_AMOUNT_RAW * 50; // 50 full tokens
+
+    await dappTokenManagerProgram.methods
+      .burnDappToken(new anchor.BN(BURN_AMOUNT_RAW))
+      .accounts({
+        userTokenAccount: user1TokenAccount,
+        mint: dappTokenMintKeypair.publicKey,
+        dappTokenManager: dappTokenManagerPda,
+        user: user1Wallet.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user1Wallet])
+      .rpc();
+
+    dappTokenManager = await dappTokenManagerProgram.account.dappTokenManager.fetch(dappTokenManagerPda);
+    dappTokenMint = await getMint(provider.connection, dappTokenMintKeypair.publicKey);
+    const currentUser1TokenAccountInfo = await getAccount(provider.connection, user1TokenAccount);
+    const expectedCirculating = (MINT_AMOUNT_RAW * 2 - BURN_AMOUNT_RAW).toString();
+
+    expect(dappTokenManager.totalBurned.toString()).to.equal(BURN_AMOUNT_RAW.toString());
+    expect(dappTokenManager.circulatingSupply.toString()).to.equal(expectedCirculating);
+    expect(dappTokenMint.supply.toString()).to.equal(expectedCirculating);
+    expect(currentUser1TokenAccountInfo.amount.toString()).to.equal(expectedCirculating);
+  });
+
+  it("Rotate a Keypair authority via proposeAuthority/acceptAuthority", async () => {
+    // NOTE authorityPda (master) can't sign from the client, so use
+    // a separate DTM whose 'authority' is a Keypair.
+    const rotationMintKeypair = anchor.web3.Keypair.generate();
+    const [rotationDtmPda] = anchor.utils.publicKey.findProgramAddressSync(
+      [
+        Buffer.from("dapp-token-manager"),
+        rotationMintKeypair.publicKey.toBuffer(),
+      ],
+      dappTokenManagerProgram.programId
+    );
+    const newAuthorityKeypair = anchor.web3.Keypair.generate();
+
+    await dappTokenManagerProgram.methods
+      .createDappTokenManager({
+        authority: authorityKeypair.publicKey,
+        supplyAmountPerMint: new anchor.BN(MINT_AMOUNT_RAW),
+        decimals: DECIMALS,
+        maxSupply: new anchor.BN(MAX_SUPPLY_RAW),
+        cooldownSeconds: new anchor.BN(COOLDOWN_SECONDS),
+        maxMintsPerUser: new anchor.BN(MAX_MINTS_PER_USER),
+        tokenMetadata: null, // no token metadata
+        tokenExtensions: null, // no token extensions (legacy Token program)
+      })
+      .accounts({
+        mint: rotationMintKeypair.publicKey,
+        dappTokenManager: rotationDtmPda,
+        authorityPayer: authorityPayer.publicKey,
+        metadata: null, // optional, no token metadata
+        tokenMetadataProgram: null,
+      })
+      .signers([rotationMintKeypair, authorityPayer])
+      .rpc();
+
+    await dappTokenManagerProgram.methods
+      .proposeAuthority(newAuthorityKeypair.publicKey)
+      .accounts({
+        dappTokenManager: rotationDtmPda,
+        authority: authorityKeypair.publicKey,
+      })
+      .signers([authorityKeypair])
+      .rpc();
+
+    let rotationDtm = await dappTokenManagerProgram.account.dappTokenManager.fetch(rotationDtmPda);
+    expect(rotationDtm.authority.toBase58()).to.equal(authorityKeypair.publicKey.toBase58());
+    expect(rotationDtm.pendingAuthority.toBase58()).to.equal(newAuthorityKeypair.publicKey.toBase58());
+
+    // Only the pending authority can accept
+    try {
+      await dappTokenManagerProgram.methods
+        .acceptAuthority()
+        .accounts({
+          dappTokenManager: rotationDtmPda,
+          pendingAuthority: user1Wallet.publicKey,
+        })
+        .signers([user1Wallet])
+        .rpc();
+      expect.fail("acceptAuthority should have been rejected");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("NotPendingAuthority");
+    }
+
+    await dappTokenManagerProgram.methods
+      .acceptAuthority()
+      .accounts({
+        dappTokenManager: rotationDtmPda,
+        pendingAuthority: newAuthorityKeypair.publicKey,
+      })
+      .signers([newAuthorityKeypair])
+      .rpc();
+
+    rotationDtm = await dappTokenManagerProgram.account.dappTokenManager.fetch(rotationDtmPda);
+    expect(rotationDtm.authority.toBase58()).to.equal(newAuthorityKeypair.publicKey.toBase58());
+    expect(rotationDtm.pendingAuthority.toBase58()).to.equal(anchor.web3.PublicKey.default.toBase58());
+
+    // Mint authority is unchanged since the DTM address didn't change
+    const rotationMint = await getMint(provider.connection, rotationMintKeypair.publicKey);
+    expect(rotationMint.mintAuthority.toBase58()).to.equal(rotationDtmPda.toBase58());
+  });
+
+  // DTM with a Keypair 'authority' so admin IXs can be signed from the client
+  const adminMintKeypair = anchor.web3.Keypair.generate();
+  const [adminDtmPda] = anchor.utils.publicKey.findProgramAddressSync(
+    [
+      Buffer.from("dapp-token-manager"),
+      adminMintKeypair.publicKey.toBuffer(),
+    ],
+    dappTokenManagerProgram.programId
+  );
+
+  it("Pause minting and update supplyAmountPerMint via updateDappTokenManager", async () => {
+    const [adminUser1MintRecordPda] = anchor.utils.publicKey.findProgramAddressSync(
+      [
+        Buffer.from("user-mint-record"),
+        adminDtmPda.toBuffer(),
+        user1Wallet.publicKey.toBuffer(),
+      ],
+      dappTokenManagerProgram.programId
+    );
+
+    const adminCreateArgs = {
+      authority: authorityKeypair.publicKey,
+      supplyAmountPerMint: new anchor.BN(MINT_AMOUNT_RAW),
+      decimals: DECIMALS,
+      maxSupply: new anchor.BN(MAX_SUPPLY_RAW),
+      cooldownSeconds: new anchor.BN(COOLDOWN_SECONDS),
+      maxMintsPerUser: new anchor.BN(MAX_MINTS_PER_USER),
+      tokenMetadata: { name: "Dapp Token", symbol: "DAPP", uri: "https://example.com/dapp.json" },
+      tokenExtensions: null, // no token extensions (legacy Token program)
+    };
+    const adminCreateAccounts = {
+      mint: adminMintKeypair.publicKey,
+      dappTokenManager: adminDtmPda,
+      authorityPayer: authorityPayer.publicKey,
+      metadata: findMetadataPda(adminMintKeypair.publicKey),
+      tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+    };
+
+    // The optional metadata accounts are required once tokenMetadata is set
+    try {
+      await dappTokenManagerProgram.methods
+        .createDappTokenManager(adminCreateArgs)
+        .accounts({ ...adminCreateAccounts, metadata: null, tokenMetadataProgram: null })
+        .signers([adminMintKeypair, authorityPayer])
+        .rpc();
+      expect.fail("tokenMetadata without the metadata accounts should have been rejected");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("MetadataAccountsRequired");
+    }
+
+    await dappTokenManagerProgram.methods
+      .createDappTokenManager(adminCreateArgs)
+      .accounts(adminCreateAccounts)
+      .signers([adminMintKeypair, authorityPayer])
+      .rpc();
+
+    // Pause and halve the per-mint amount
+    await dappTokenManagerProgram.methods
+      .updateDappTokenManager(new anchor.BN(MINT_AMOUNT_RAW / 2), true)
+      .accounts({
+        dappTokenManager: adminDtmPda,
+        authority: authorityKeypair.publicKey,
+      })
+      .signers([authorityKeypair])
+      .rpc();
+
+    let adminDtm = await dappTokenManagerProgram.account.dappTokenManager.fetch(adminDtmPda);
+    expect(adminDtm.paused).to.equal(true);
+    expect(adminDtm.supplyAmountPerMint.toString()).to.equal((MINT_AMOUNT_RAW / 2).toString());
+
+    const mintAccounts = {
+      userTokenAccount: getAssociatedTokenAddressSync(adminMintKeypair.publicKey, user1Wallet.publicKey),
+      mint: adminMintKeypair.publicKey,
+      dappTokenManager: adminDtmPda,
+      userMintRecord: adminUser1MintRecordPda,
+      user: user1Wallet.publicKey,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+
+    try {
+      await dappTokenManagerProgram.methods
+        .mintDappTokenSupply()
+        .accounts(mintAccounts)
+        .signers([user1Wallet])
+        .rpc();
+      expect.fail("Mint should have been rejected while paused");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("Paused");
+    }
+
+    // Unpause only (leave supplyAmountPerMint as is)
+    await dappTokenManagerProgram.methods
+      .updateDappTokenManager(null, false)
+      .accounts({
+        dappTokenManager: adminDtmPda,
+        authority: authorityKeypair.publicKey,
+      })
+      .signers([authorityKeypair])
+      .rpc();
+
+    // NOTE Listen for the DappTokenSupplyMinted event (decoded via IDL)
+    let mintedEvent: any;
+    const listener = dappTokenManagerProgram.addEventListener(
+      "DappTokenSupplyMinted",
+      (event) => { mintedEvent = event; }
+    );
+
+    await dappTokenManagerProgram.methods
+      .mintDappTokenSupply()
+      .accounts(mintAccounts)
+      .signers([user1Wallet])
+      .rpc();
+
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+    await dappTokenManagerProgram.removeEventListener(listener);
+    expect(mintedEvent.dappTokenManager.toBase58()).to.equal(adminDtmPda.toBase58());
+    expect(mintedEvent.user.toBase58()).to.equal(user1Wallet.publicKey.toBase58());
+    expect(mintedEvent.amount.toString()).to.equal((MINT_AMOUNT_RAW / 2).toString());
+    expect(mintedEvent.totalMintCount.toNumber()).to.equal(1);
+
+    adminDtm = await dappTokenManagerProgram.account.dappTokenManager.fetch(adminDtmPda);
+    expect(adminDtm.paused).to.equal(false);
+    expect(adminDtm.totalMintCount.toNumber()).to.equal(1);
+    const adminMint = await getMint(provider.connection, adminMintKeypair.publicKey);
+    expect(adminMint.supply.toString()).to.equal((MINT_AMOUNT_RAW / 2).toString());
+  });
+
+  it("Freeze and thaw user1 admin token account via the DTM PDA", async () => {
+    const adminUser1TokenAccount = getAssociatedTokenAddressSync(
+      adminMintKeypair.publicKey,
+      user1Wallet.publicKey
+    );
+    const freezeAccounts = {
+      userTokenAccount: adminUser1TokenAccount,
+      mint: adminMintKeypair.publicKey,
+      dappTokenManager: adminDtmPda,
+      authority: authorityKeypair.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+
+    // Only the DTM authority can freeze
+    try {
+      await dappTokenManagerProgram.methods
+        .freezeUserAccount()
+        .accounts({ ...freezeAccounts, authority: user1Wallet.publicKey })
+        .signers([user1Wallet])
+        .rpc();
+      expect.fail("freezeUserAccount should have been rejected");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("Unauthorized");
+    }
+
+    await dappTokenManagerProgram.methods
+      .freezeUserAccount()
+      .accounts(freezeAccounts)
+      .signers([authorityKeypair])
+      .rpc();
+    expect((await getAccount(provider.connection, adminUser1TokenAccount)).isFrozen).to.equal(true);
+
+    await dappTokenManagerProgram.methods
+      .thawUserAccount()
+      .accounts(freezeAccounts)
+      .signers([authorityKeypair])
+      .rpc();
+    expect((await getAccount(provider.connection, adminUser1TokenAccount)).isFrozen).to.equal(false);
+  });
+
+  it("Create and update admin mint token metadata (DTM PDA is update authority)", async () => {
+    const adminMetadataPda = findMetadataPda(adminMintKeypair.publicKey);
+
+    // Created during createDappTokenManager above
+    let adminMetadataInfo = await provider.connection.getAccountInfo(adminMetadataPda);
+    expect(adminMetadataInfo.owner.toBase58()).to.equal(TOKEN_METADATA_PROGRAM_ID.toBase58());
+    expect(adminMetadataInfo.data.includes(Buffer.from("DAPP"))).to.equal(true);
+
+    await dappTokenManagerProgram.methods
+      .updateDappTokenMetadata({
+        name: "Dapp Token S2",
+        symbol: "DAPP2",
+        uri: "https://example.com/dapp2.json",
+      })
+      .accounts({
+        dappTokenManager: adminDtmPda,
+        metadata: adminMetadataPda,
+        authority: authorityKeypair.publicKey,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      })
+      .signers([authorityKeypair])
+      .rpc();
+
+    adminMetadataInfo = await provider.connection.getAccountInfo(adminMetadataPda);
+    expect(adminMetadataInfo.data.includes(Buffer.from("Dapp Token S2"))).to.equal(true);
+    expect(adminMetadataInfo.data.includes(Buffer.from("DAPP2"))).to.equal(true);
+  });
+
+  it("Token-2022: create a DTM with transfer fee + interest-bearing extensions, then mint, burn, freeze", async () => {
+    const mint2022Keypair = anchor.web3.Keypair.generate();
+    const [dtm2022Pda] = anchor.utils.publicKey.findProgramAddressSync(
+      [Buffer.from("dapp-token-manager"), mint2022Keypair.publicKey.toBuffer()],
+      dappTokenManagerProgram.programId
+    );
+    const [user1MintRecord2022Pda] = anchor.utils.publicKey.findProgramAddressSync(
+      [Buffer.from("user-mint-record"), dtm2022Pda.toBuffer(), user1Wallet.publicKey.toBuffer()],
+      dappTokenManagerProgram.programId
+    );
+    const TRANSFER_FEE_BASIS_POINTS = 50; // 0.5%
+    const TRANSFER_FEE_MAXIMUM_RAW = ONE_TOKEN_AMOUNT_RAW;
+    const INTEREST_RATE_BASIS_POINTS = 250; // 2.5%
+
+    await dappTokenManagerProgram.methods
+      .createDappTokenManager({
+        authority: authorityKeypair.publicKey,
+        supplyAmountPerMint: new anchor.BN(MINT_AMOUNT_RAW),
+        decimals: DECIMALS,
+        maxSupply: new anchor.BN(MAX_SUPPLY_RAW),
+        cooldownSeconds: new anchor.BN(0), // no cooldown
+        maxMintsPerUser: new anchor.BN(0), // unlimited mints per user
+        tokenMetadata: null, // NOTE Token Metadata only supports legacy Token mints
+        tokenExtensions: {
+          transferFee: {
+            basisPoints: TRANSFER_FEE_BASIS_POINTS,
+            maximumFee: new anchor.BN(TRANSFER_FEE_MAXIMUM_RAW),
+          },
+          nonTransferable: false,
+          interestRate: INTEREST_RATE_BASIS_POINTS,
+        },
+      })
+      .accounts({
+        mint: mint2022Keypair.publicKey,
+        dappTokenManager: dtm2022Pda,
+        authorityPayer: authorityPayer.publicKey,
+        metadata: null, // optional, no token metadata
+        tokenMetadataProgram: null,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([mint2022Keypair, authorityPayer])
+      .rpc();
+
+    const dtm2022 = await dappTokenManagerProgram.account.dappTokenManager.fetch(dtm2022Pda);
+    expect(dtm2022.tokenProgram.toBase58()).to.equal(TOKEN_2022_PROGRAM_ID.toBase58());
+
+    const mintInfo = await provider.connection.getAccountInfo(mint2022Keypair.publicKey);
+    expect(mintInfo.owner.toBase58()).to.equal(TOKEN_2022_PROGRAM_ID.toBase58());
+    const mint2022 = await getMint(provider.connection, mint2022Keypair.publicKey, undefined, TOKEN_2022_PROGRAM_ID);
+    expect(mint2022.mintAuthority.toBase58()).to.equal(dtm2022Pda.toBase58());
+    expect(mint2022.freezeAuthority.toBase58()).to.equal(dtm2022Pda.toBase58());
+
+    const transferFeeConfig = getTransferFeeConfig(mint2022);
+    expect(transferFeeConfig.transferFeeConfigAuthority.toBase58()).to.equal(dtm2022Pda.toBase58());
+    expect(transferFeeConfig.withdrawWithheldAuthority.toBase58()).to.equal(dtm2022Pda.toBase58());
+    expect(transferFeeConfig.newerTransferFee.transferFeeBasisPoints).to.equal(TRANSFER_FEE_BASIS_POINTS);
+    expect(transferFeeConfig.newerTransferFee.maximumFee.toString()).to.equal(TRANSFER_FEE_MAXIMUM_RAW.toString());
+    const interestBearingConfig = getInterestBearingMintConfigState(mint2022);
+    expect(interestBearingConfig.rateAuthority.toBase58()).to.equal(dtm2022Pda.toBase58());
+    expect(interestBearingConfig.currentRate).to.equal(INTEREST_RATE_BASIS_POINTS);
+    expect(getNonTransferable(mint2022)).to.equal(null);
+
+    // NOTE The ATA is derived with the Token-2022 program id
+    const user1TokenAccount2022 = getAssociatedTokenAddressSync(
+      mint2022Keypair.publicKey,
+      user1Wallet.publicKey,
+      false,
+      TOKEN_2022_PROGRAM_ID
+    );
+    const mint2022Accounts = {
+      userTokenAccount: user1TokenAccount2022,
+      mint: mint2022Keypair.publicKey,
+      dappTokenManager: dtm2022Pda,
+      userMintRecord: user1MintRecord2022Pda,
+      user: user1Wallet.publicKey,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+    };
+
+    // The DTM pins the token program that owns its mint
+    try {
+      await dappTokenManagerProgram.methods
+        .mintDappTokenSupply()
+        .accounts({ ...mint2022Accounts, tokenProgram: TOKEN_PROGRAM_ID })
+        .signers([user1Wallet])
+        .rpc();
+      expect.fail("mintDappTokenSupply with the legacy Token program should have been rejected");
+    } catch (err: any) {
+      // NOTE The ATA address is derived from tokenProgram, so it may fail that check first
+      expect(err.error.errorCode.code).to.be.oneOf(["InvalidTokenAccount", "TokenProgramMismatch"]);
+    }
+
+    await dappTokenManagerProgram.methods
+      .mintDappTokenSupply()
+      .accounts(mint2022Accounts)
+      .signers([user1Wallet])
+      .rpc();
+
+    let user1TokenAccount2022Info = await getAccount(
+      provider.connection, user1TokenAccount2022, undefined, TOKEN_2022_PROGRAM_ID
+    );
+    expect(user1TokenAccount2022Info.amount.toString()).to.equal(MINT_AMOUNT_RAW.toString());
+
+    const BURN_AMOUNT_RAW = ONE_TOKEN_AMOUNT_RAW * 10;
+    await dappTokenManagerProgram.methods
+      .burnDappToken(new anchor.BN(BURN_AMOUNT_RAW))
+      .accounts({
+        userTokenAccount: user1TokenAccount2022,
+        mint: mint2022Keypair.publicKey,
+        dappTokenManager: dtm2022Pda,
+        user: user1Wallet.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([user1Wallet])
+      .rpc();
+    const dtm2022AfterBurn = await dappTokenManagerProgram.account.dappTokenManager.fetch(dtm2022Pda);
+    expect(dtm2022AfterBurn.circulatingSupply.toString()).to.equal((MINT_AMOUNT_RAW - BURN_AMOUNT_RAW).toString());
+
+    const freeze2022Accounts = {
+      userTokenAccount: user1TokenAccount2022,
+      mint: mint2022Keypair.publicKey,
+      dappTokenManager: dtm2022Pda,
+      authority: authorityKeypair.publicKey,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+    };
+    await dappTokenManagerProgram.methods
+      .freezeUserAccount()
+      .accounts(freeze2022Accounts)
+      .signers([authorityKeypair])
+      .rpc();
+    user1TokenAccount2022Info = await getAccount(
+      provider.connection, user1TokenAccount2022, undefined, TOKEN_2022_PROGRAM_ID
+    );
+    expect(user1TokenAccount2022Info.isFrozen).to.equal(true);
+
+    await dappTokenManagerProgram.methods
+      .thawUserAccount()
+      .accounts(freeze2022Accounts)
+      .signers([authorityKeypair])
+      .rpc();
+    user1TokenAccount2022Info = await getAccount(
+      provider.connection, user1TokenAccount2022, undefined, TOKEN_2022_PROGRAM_ID
+    );
+    expect(user1TokenAccount2022Info.isFrozen).to.equal(false);
   });
 
+  it("Token-2022: create a non-transferable DTM and reject invalid token program/extension combos", async () => {
+    const createAccounts = (mint: anchor.web3.PublicKey, tokenProgram: anchor.web3.PublicKey) => ({
+      mint,
+      dappTokenManager: anchor.utils.publicKey.findProgramAddressSync(
+        [Buffer.from("dapp-token-manager"), mint.toBuffer()],
+        dappTokenManagerProgram.programId
+      )[0],
+      authorityPayer: authorityPayer.publicKey,
+      metadata: findMetadataPda(mint),
+      tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      tokenProgram,
+    });
+    const createDtm = (
+      mintKeypair: anchor.web3.Keypair,
+      tokenProgram: anchor.web3.PublicKey,
+      tokenMetadata: { name: string; symbol: string; uri: string } | null,
+      tokenExtensions: any
+    ) =>
+      dappTokenManagerProgram.methods
+        .createDappTokenManager({
+          authority: authorityKeypair.publicKey,
+          supplyAmountPerMint: new anchor.BN(MINT_AMOUNT_RAW),
+          decimals: DECIMALS,
+          maxSupply: new anchor.BN(MAX_SUPPLY_RAW),
+          cooldownSeconds: new anchor.BN(0),
+          maxMintsPerUser: new anchor.BN(0),
+          tokenMetadata,
+          tokenExtensions,
+        })
+        .accounts(createAccounts(mintKeypair.publicKey, tokenProgram))
+        .signers([mintKeypair, authorityPayer])
+        .rpc();
+    const nonTransferable = { transferFee: null, nonTransferable: true, interestRate: null };
 
+    // Extensions require Token-2022
+    try {
+      await createDtm(anchor.web3.Keypair.generate(), TOKEN_PROGRAM_ID, null, nonTransferable);
+      expect.fail("Extensions on a legacy Token mint should have been rejected");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("ExtensionsRequireToken2022");
+    }
+
+    // Token Metadata (pinned ~1.8) requires legacy Token
+    try {
+      await createDtm(
+        anchor.web3.Keypair.generate(),
+        TOKEN_2022_PROGRAM_ID,
+        { name: "Dapp Token 2022", symbol: "DAPP22", uri: "https://example.com/dapp22.json" },
+        null
+      );
+      expect.fail("Token metadata on a Token-2022 mint should have been rejected");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("MetadataRequiresLegacyToken");
+    }
+
+    // Any other program isn't a token program
+    try {
+      await createDtm(anchor.web3.Keypair.generate(), anchor.web3.SystemProgram.programId, null, null);
+      expect.fail("A non-token program should have been rejected");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("UnsupportedTokenProgram");
+    }
+
+    const soulboundMintKeypair = anchor.web3.Keypair.generate();
+    await createDtm(soulboundMintKeypair, TOKEN_2022_PROGRAM_ID, null, nonTransferable);
+    const soulboundMint = await getMint(
+      provider.connection, soulboundMintKeypair.publicKey, undefined, TOKEN_2022_PROGRAM_ID
+    );
+    expect(getNonTransferable(soulboundMint)).to.not.equal(null);
+    expect(getTransferFeeConfig(soulboundMint)).to.equal(null);
+  });
+
+  it("Master: create and mint a managed token end to end (master PDA is DTM authority)", async () => {
+    const managedMintKeypair = anchor.web3.Keypair.generate();
+    const [managedTokenAuthorityPda] = anchor.utils.publicKey.findProgramAddressSync(
+      [Buffer.from("managed-token-authority")],
+      masterProgram.programId
+    );
+    const [managedDtmPda] = anchor.utils.publicKey.findProgramAddressSync(
+      [Buffer.from("dapp-token-manager"), managedMintKeypair.publicKey.toBuffer()],
+      dappTokenManagerProgram.programId
+    );
+    const [managedUser1MintRecordPda] = anchor.utils.publicKey.findProgramAddressSync(
+      [
+        Buffer.from("user-mint-record"),
+        managedDtmPda.toBuffer(),
+        user1Wallet.publicKey.toBuffer(),
+      ],
+      dappTokenManagerProgram.programId
+    );
+    const managedUser1TokenAccount = getAssociatedTokenAddressSync(
+      managedMintKeypair.publicKey,
+      user1Wallet.publicKey,
+    );
+    const [managedTokenPda] = anchor.utils.publicKey.findProgramAddressSync(
+      [Buffer.from("managed-token"), managedDtmPda.toBuffer()],
+      masterProgram.programId
+    );
+
+    await masterProgram.methods
+      .createManagedToken({
+        // Overridden by master with managedTokenAuthorityPda
+        authority: authorityPayer.publicKey,
+        supplyAmountPerMint: new anchor.BN(MINT_AMOUNT_RAW),
+        decimals: DECIMALS,
+        maxSupply: new anchor.BN(MAX_SUPPLY_RAW),
+        cooldownSeconds: new anchor.BN(COOLDOWN_SECONDS),
+        maxMintsPerUser: new anchor.BN(MAX_MINTS_PER_USER),
+        tokenMetadata: null, // no token metadata
+        tokenExtensions: null, // ignored, managed tokens stay on legacy Token
+      })
+      .accounts({
+        dappTokenManagerProgram: dappTokenManagerProgram.programId,
+        mint: managedMintKeypair.publicKey,
+        dappTokenManager: managedDtmPda,
+        managedTokenAuthority: managedTokenAuthorityPda,
+        managedToken: managedTokenPda,
+        authorityPayer: authorityPayer.publicKey,
+        metadata: null, // optional, no token metadata
+        tokenMetadataProgram: null,
+      })
+      .signers([managedMintKeypair, authorityPayer])
+      .rpc();
+
+    const managedDtm = await dappTokenManagerProgram.account.dappTokenManager.fetch(managedDtmPda);
+    expect(managedDtm.authority.toBase58()).to.equal(managedTokenAuthorityPda.toBase58());
+    expect(managedDtm.mint.toBase58()).to.equal(managedMintKeypair.publicKey.toBase58());
+    const managedToken = await masterProgram.account.managedToken.fetch(managedTokenPda);
+    expect(managedToken.creator.toBase58()).to.equal(authorityPayer.publicKey.toBase58());
+    expect(managedToken.dappTokenManager.toBase58()).to.equal(managedDtmPda.toBase58());
+
+    // ATA + mint record don't exist yet. DTMP init_if_needed's them via CPI
+    await masterProgram.methods
+      .mintManagedToken()
+      .accounts({
+        dappTokenManagerProgram: dappTokenManagerProgram.programId,
+        mint: managedMintKeypair.publicKey,
+        dappTokenManager: managedDtmPda,
+        managedTokenAuthority: managedTokenAuthorityPda,
+        userTokenAccount: managedUser1TokenAccount,
+        userMintRecord: managedUser1MintRecordPda,
+        user: user1Wallet.publicKey,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user1Wallet])
+      .rpc();
+
+    const managedUser1TokenAccountInfo = await getAccount(
+      provider.connection,
+      managedUser1TokenAccount
+    );
+    expect(managedUser1TokenAccountInfo.amount.toString()).to.equal(MINT_AMOUNT_RAW.toString());
+    expect((await dappTokenManagerProgram.account.dappTokenManager.fetch(managedDtmPda))
+      .totalMintCount.toNumber()).to.equal(1);
+
+    // DTMs not managed by master are rejected
+    try {
+      await masterProgram.methods
+        .mintManagedToken()
+        .accounts({
+          dappTokenManagerProgram: dappTokenManagerProgram.programId,
+          mint: adminMintKeypair.publicKey,
+          dappTokenManager: adminDtmPda,
+          managedTokenAuthority: managedTokenAuthorityPda,
+          userTokenAccount: getAssociatedTokenAddressSync(
+            adminMintKeypair.publicKey,
+            user1Wallet.publicKey,
+          ),
+          userMintRecord: anchor.utils.publicKey.findProgramAddressSync(
+            [
+              Buffer.from("user-mint-record"),
+              adminDtmPda.toBuffer(),
+              user1Wallet.publicKey.toBuffer(),
+            ],
+            dappTokenManagerProgram.programId
+          )[0],
+          user: user1Wallet.publicKey,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user1Wallet])
+        .rpc();
+      expect.fail("mintManagedToken should have failed with NotManagedToken");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("NotManagedToken");
+    }
+
+    // ==== Admin ops go through master, and only the creator may run them ====
+    const manageAccounts = {
+      dappTokenManagerProgram: dappTokenManagerProgram.programId,
+      dappTokenManager: managedDtmPda,
+      managedToken: managedTokenPda,
+      managedTokenAuthority: managedTokenAuthorityPda,
+      creator: authorityPayer.publicKey,
+    };
+
+    try {
+      await masterProgram.methods
+        .updateManagedToken(null, true)
+        .accounts({ ...manageAccounts, creator: user1Wallet.publicKey })
+        .signers([user1Wallet])
+        .rpc();
+      expect.fail("updateManagedToken should have failed with Unauthorized");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("Unauthorized");
+    }
+
+    await masterProgram.methods
+      .updateManagedToken(new anchor.BN(MINT_AMOUNT_RAW * 2), true)
+      .accounts(manageAccounts)
+      .signers([authorityPayer])
+      .rpc();
+    let updatedDtm = await dappTokenManagerProgram.account.dappTokenManager.fetch(managedDtmPda);
+    expect(updatedDtm.paused).to.equal(true);
+    expect(updatedDtm.supplyAmountPerMint.toNumber()).to.equal(MINT_AMOUNT_RAW * 2);
+
+    await masterProgram.methods
+      .updateManagedToken(null, false)
+      .accounts(manageAccounts)
+      .signers([authorityPayer])
+      .rpc();
+    expect((await dappTokenManagerProgram.account.dappTokenManager.fetch(managedDtmPda))
+      .paused).to.equal(false);
+
+    const manageTokenAccountAccounts = {
+      ...manageAccounts,
+      mint: managedMintKeypair.publicKey,
+      userTokenAccount: managedUser1TokenAccount,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+    await masterProgram.methods
+      .freezeManagedTokenAccount()
+      .accounts(manageTokenAccountAccounts)
+      .signers([authorityPayer])
+      .rpc();
+    expect((await getAccount(provider.connection, managedUser1TokenAccount)).isFrozen).to.equal(true);
+
+    await masterProgram.methods
+      .thawManagedTokenAccount()
+      .accounts(manageTokenAccountAccounts)
+      .signers([authorityPayer])
+      .rpc();
+    expect((await getAccount(provider.connection, managedUser1TokenAccount)).isFrozen).to.equal(false);
+
+    // Hand the DTM to a Keypair authority, then bring it back under master
+    await masterProgram.methods
+      .proposeManagedTokenAuthority(authorityKeypair.publicKey)
+      .accounts(manageAccounts)
+      .signers([authorityPayer])
+      .rpc();
+    await dappTokenManagerProgram.methods
+      .acceptAuthority()
+      .accounts({
+        dappTokenManager: managedDtmPda,
+        pendingAuthority: authorityKeypair.publicKey,
+      })
+      .signers([authorityKeypair])
+      .rpc();
+    updatedDtm = await dappTokenManagerProgram.account.dappTokenManager.fetch(managedDtmPda);
+    expect(updatedDtm.authority.toBase58()).to.equal(authorityKeypair.publicKey.toBase58());
+
+    await dappTokenManagerProgram.methods
+      .proposeAuthority(managedTokenAuthorityPda)
+      .accounts({
+        dappTokenManager: managedDtmPda,
+        authority: authorityKeypair.publicKey,
+      })
+      .signers([authorityKeypair])
+      .rpc();
+    await masterProgram.methods
+      .acceptManagedTokenAuthority()
+      .accounts(manageAccounts)
+      .signers([authorityPayer])
+      .rpc();
+    updatedDtm = await dappTokenManagerProgram.account.dappTokenManager.fetch(managedDtmPda);
+    expect(updatedDtm.authority.toBase58()).to.equal(managedTokenAuthorityPda.toBase58());
+  });
 
 });
@@ -24,11 +24,18 @@ describe('puppet', () => {
   // Doing this allows the masterProgram's pullStrings() method ix to be signed by
   // the 'authority' PDA, which is derived from masterProgram.programId + bump
   it('CPI: authority as PDA', async () => {
-    const [puppetMasterPDA, puppetMasterBump] = await PublicKey
-      .findProgramAddress([], masterProgram.programId);
+    // U: Per-puppet authority PDA seeded by the puppet key.
+    // master_program stores the canonical bump in initializePuppetAuthority(),
+    // so pullStrings() no longer takes a caller-supplied bump.
+    const [puppetAuthorityPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("puppet-authority"), puppetKeypair.publicKey.toBuffer()],
+      masterProgram.programId
+    );
 
+    // U: The wallet controls the puppet first, then initializePuppetAuthority()
+    // hands it to the PDA and records the wallet as owner in one IX
     await puppetProgram.methods
-      .initialize(puppetMasterPDA)
+      .initialize(provider.wallet.publicKey)
       .accounts({
         puppet: puppetKeypair.publicKey,
         user: provider.wallet.publicKey,
@@ -36,37 +43,1118 @@ describe('puppet', () => {
       .signers([puppetKeypair])
       .rpc();
 
-    // Q: Can I use the same PDA authority used to initialize
-    // the puppet account to also set_data() even though the 
-    // SetData Context struct has authority: Signer type
-    // A: NO! Would have to pass authorityKeypair.publicKey
-    // **See Scenario A below**
-    // await puppetProgram.methods
-    //   .setData(new anchor.BN(18))
-    //   .accounts({
-    //     puppet: puppetKeypair.publicKey,
-    //     authority: puppetMasterPDA
-    //   })
-    //   .rpc();
+    // Only the puppet's current authority can hand it to master
+    const strangerKeypair = Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(strangerKeypair.publicKey, anchor.web3.LAMPORTS_PER_SOL)
+    );
+    try {
+      await masterProgram.methods
+        .initializePuppetAuthority()
+        .accounts({
+          puppet: puppetKeypair.publicKey,
+          puppetProgram: puppetProgram.programId,
+          puppetAuthority: puppetAuthorityPda,
+          owner: strangerKeypair.publicKey,
+        })
+        .signers([strangerKeypair])
+        .rpc();
+      expect.fail("initializePuppetAuthority should have failed with Unauthorized");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("Unauthorized");
+    }
 
-    // expect((await puppetProgram.account.puppet
-    //   .fetch(puppetKeypair.publicKey)).data.toNumber()).to.equal(18);
+    await masterProgram.methods
+      .initializePuppetAuthority()
+      .accounts({
+        puppet: puppetKeypair.publicKey,
+        puppetProgram: puppetProgram.programId,
+        puppetAuthority: puppetAuthorityPda,
+        owner: provider.wallet.publicKey,
+      })
+      .rpc();
 
+    expect((await puppetProgram.account.puppet
+      .fetch(puppetKeypair.publicKey)).authority.toBase58()).to.equal(puppetAuthorityPda.toBase58());
+    expect((await masterProgram.account.puppetAuthority
+      .fetch(puppetAuthorityPda)).owner.toBase58()).to.equal(provider.wallet.publicKey.toBase58());
 
     await masterProgram.methods
-      .pullStrings(puppetMasterBump, new anchor.BN(42))
+      .pullStrings(new anchor.BN(42))
       .accounts({
         puppetProgram: puppetProgram.programId,
         puppet: puppetKeypair.publicKey,
-        authority: puppetMasterPDA // <-- Now just PDA. CPI sets 'authority' PDA
+        authority: puppetAuthorityPda, // <-- Now just PDA. CPI sets 'authority' PDA
         // account 'is_signer = true', so masterProgram can sign with 'authority'
+        owner: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    expect((await puppetProgram.account.puppet
+      .fetch(puppetKeypair.publicKey)).data.toNumber()).to.equal(42);
+  });
+
+  it('CPI: pullStrings returns previous and new data', async () => {
+    const [puppetAuthorityPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("puppet-authority"), puppetKeypair.publicKey.toBuffer()],
+      masterProgram.programId
+    );
+
+    // NOTE view() requires all accounts to be immutable, so simulate()
+    // and decode master's return data from the 'Program return:' log
+    const simulation = await masterProgram.methods
+      .pullStrings(new anchor.BN(43))
+      .accounts({
+        puppetProgram: puppetProgram.programId,
+        puppet: puppetKeypair.publicKey,
+        authority: puppetAuthorityPda,
+        owner: provider.wallet.publicKey,
+      })
+      .simulate();
+
+    const returnPrefix = `Program return: ${masterProgram.programId.toBase58()} `;
+    const returnLog = simulation.raw.find((log) => log.startsWith(returnPrefix));
+    expect(returnLog).to.not.be.undefined;
+    const returnData = Buffer.from(returnLog.slice(returnPrefix.length), "base64");
+    const result = masterProgram.coder.types.decode("PullStringsResult", returnData);
+    expect(result.previousData.toNumber()).to.equal(42);
+    expect(result.newData.toNumber()).to.equal(43);
+
+    // Simulation doesn't persist state
+    expect((await puppetProgram.account.puppet
+      .fetch(puppetKeypair.publicKey)).data.toNumber()).to.equal(42);
+  });
+
+  it('CPI: compareAndPullStrings only writes when expected matches', async () => {
+    const [puppetAuthorityPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("puppet-authority"), puppetKeypair.publicKey.toBuffer()],
+      masterProgram.programId
+    );
+    const versionBefore = (await puppetProgram.account.puppet
+      .fetch(puppetKeypair.publicKey)).version.toNumber();
+
+    // Stale 'expected' value is rejected
+    try {
+      await masterProgram.methods
+        .compareAndPullStrings(new anchor.BN(1), new anchor.BN(100))
+        .accounts({
+          puppetProgram: puppetProgram.programId,
+          puppet: puppetKeypair.publicKey,
+          authority: puppetAuthorityPda,
+          owner: provider.wallet.publicKey,
+        })
+        .rpc();
+      expect.fail("compareAndPullStrings should have failed with DataMismatch");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("DataMismatch");
+    }
+
+    let puppet = await puppetProgram.account.puppet.fetch(puppetKeypair.publicKey);
+    expect(puppet.data.toNumber()).to.equal(42);
+    expect(puppet.version.toNumber()).to.equal(versionBefore);
+
+    // Matching 'expected' value swaps and bumps version
+    await masterProgram.methods
+      .compareAndPullStrings(new anchor.BN(42), new anchor.BN(100))
+      .accounts({
+        puppetProgram: puppetProgram.programId,
+        puppet: puppetKeypair.publicKey,
+        authority: puppetAuthorityPda,
+        owner: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    puppet = await puppetProgram.account.puppet.fetch(puppetKeypair.publicKey);
+    expect(puppet.data.toNumber()).to.equal(100);
+    expect(puppet.version.toNumber()).to.equal(versionBefore + 1);
+
+    // Restore for the following tests
+    await masterProgram.methods
+      .pullStrings(new anchor.BN(42))
+      .accounts({
+        puppetProgram: puppetProgram.programId,
+        puppet: puppetKeypair.publicKey,
+        authority: puppetAuthorityPda,
+        owner: provider.wallet.publicKey,
+      })
+      .rpc();
+  });
+
+  it('CPI: pullPayload reallocs the puppet to fit the payload', async () => {
+    const [puppetAuthorityPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("puppet-authority"), puppetKeypair.publicKey.toBuffer()],
+      masterProgram.programId
+    );
+    // discriminator + data + authority + version + has_history + vec prefix
+    const baseSpace = 8 + 8 + 32 + 8 + 1 + 4;
+
+    // Grow
+    const payload = Buffer.from("hello, puppet".repeat(10));
+    await masterProgram.methods
+      .pullPayload(payload)
+      .accounts({
+        puppet: puppetKeypair.publicKey,
+        puppetProgram: puppetProgram.programId,
+        authority: puppetAuthorityPda,
+        owner: provider.wallet.publicKey,
+        payer: provider.wallet.publicKey,
       })
       .rpc();
 
+    let puppetInfo = await provider.connection.getAccountInfo(puppetKeypair.publicKey);
+    expect(puppetInfo.data.length).to.equal(baseSpace + payload.length);
+    let puppet = await puppetProgram.account.puppet.fetch(puppetKeypair.publicKey);
+    expect(Buffer.from(puppet.payload).toString()).to.equal(payload.toString());
+    expect(puppet.data.toNumber()).to.equal(42);
+
+    // Shrink: excess rent goes back to payer
+    const grownLamports = puppetInfo.lamports;
+    await masterProgram.methods
+      .pullPayload(Buffer.from("hi"))
+      .accounts({
+        puppet: puppetKeypair.publicKey,
+        puppetProgram: puppetProgram.programId,
+        authority: puppetAuthorityPda,
+        owner: provider.wallet.publicKey,
+        payer: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    puppetInfo = await provider.connection.getAccountInfo(puppetKeypair.publicKey);
+    expect(puppetInfo.data.length).to.equal(baseSpace + 2);
+    expect(puppetInfo.lamports).to.equal(
+      await provider.connection.getMinimumBalanceForRentExemption(baseSpace + 2)
+    );
+    expect(puppetInfo.lamports).to.be.lessThan(grownLamports);
+
+    // Over the cap
+    try {
+      await masterProgram.methods
+        .pullPayload(Buffer.alloc(513))
+        .accounts({
+          puppet: puppetKeypair.publicKey,
+          puppetProgram: puppetProgram.programId,
+          authority: puppetAuthorityPda,
+          owner: provider.wallet.publicKey,
+          payer: provider.wallet.publicKey,
+        })
+        .rpc();
+      expect.fail("pullPayload should have failed with PayloadTooLarge");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("PayloadTooLarge");
+    }
+  });
+
+  it('CPI: rejects a non-canonical authority PDA', async () => {
+    // The old scheme's PDA ([] seeds) is no longer accepted
+    const [legacyMasterPda] = PublicKey.findProgramAddressSync([], masterProgram.programId);
+
+    try {
+      await masterProgram.methods
+        .pullStrings(new anchor.BN(7))
+        .accounts({
+          puppetProgram: puppetProgram.programId,
+          puppet: puppetKeypair.publicKey,
+          authority: legacyMasterPda,
+          owner: provider.wallet.publicKey,
+        })
+        .rpc();
+      expect.fail("pullStrings should have been rejected");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("AccountNotInitialized");
+    }
+
+    expect((await puppetProgram.account.puppet
+      .fetch(puppetKeypair.publicKey)).data.toNumber()).to.equal(42);
+  });
+
+  it('CPI: only the PuppetAuthority owner can pull strings', async () => {
+    const [puppetAuthorityPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("puppet-authority"), puppetKeypair.publicKey.toBuffer()],
+      masterProgram.programId
+    );
+    const strangerKeypair = Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(strangerKeypair.publicKey, anchor.web3.LAMPORTS_PER_SOL)
+    );
+
+    try {
+      await masterProgram.methods
+        .pullStrings(new anchor.BN(7))
+        .accounts({
+          puppetProgram: puppetProgram.programId,
+          puppet: puppetKeypair.publicKey,
+          authority: puppetAuthorityPda,
+          owner: strangerKeypair.publicKey,
+        })
+        .signers([strangerKeypair])
+        .rpc();
+      expect.fail("pullStrings should have failed with Unauthorized");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("Unauthorized");
+    }
+
+    try {
+      await masterProgram.methods
+        .pullPayload(Buffer.from("pwned"))
+        .accounts({
+          puppet: puppetKeypair.publicKey,
+          puppetProgram: puppetProgram.programId,
+          authority: puppetAuthorityPda,
+          owner: strangerKeypair.publicKey,
+          payer: strangerKeypair.publicKey,
+        })
+        .signers([strangerKeypair])
+        .rpc();
+      expect.fail("pullPayload should have failed with Unauthorized");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("Unauthorized");
+    }
+
+    try {
+      await masterProgram.methods
+        .pullStringsBatch([new anchor.BN(7)])
+        .accounts({ puppetProgram: puppetProgram.programId, owner: strangerKeypair.publicKey })
+        .remainingAccounts([
+          { pubkey: puppetKeypair.publicKey, isWritable: true, isSigner: false },
+          { pubkey: puppetAuthorityPda, isWritable: false, isSigner: false },
+        ])
+        .signers([strangerKeypair])
+        .rpc();
+      expect.fail("pullStringsBatch should have failed with Unauthorized");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("Unauthorized");
+    }
+
     expect((await puppetProgram.account.puppet
       .fetch(puppetKeypair.publicKey)).data.toNumber()).to.equal(42);
   });
 
+  it('Transfers puppet authority and closes the puppet', async () => {
+    const retiredPuppetKeypair = Keypair.generate();
+    const oldAuthorityKeypair = Keypair.generate();
+    const newAuthorityKeypair = Keypair.generate();
+    const receiverKeypair = Keypair.generate();
+
+    await puppetProgram.methods
+      .initialize(oldAuthorityKeypair.publicKey)
+      .accounts({
+        puppet: retiredPuppetKeypair.publicKey,
+        user: provider.wallet.publicKey,
+      })
+      .signers([retiredPuppetKeypair])
+      .rpc();
+
+    await puppetProgram.methods
+      .transferPuppetAuthority(newAuthorityKeypair.publicKey)
+      .accounts({
+        puppet: retiredPuppetKeypair.publicKey,
+        authority: oldAuthorityKeypair.publicKey,
+      })
+      .signers([oldAuthorityKeypair])
+      .rpc();
+
+    expect((await puppetProgram.account.puppet
+      .fetch(retiredPuppetKeypair.publicKey)).authority.toBase58())
+      .to.equal(newAuthorityKeypair.publicKey.toBase58());
+
+    // Old authority can no longer close
+    try {
+      await puppetProgram.methods
+        .closePuppet()
+        .accounts({
+          puppet: retiredPuppetKeypair.publicKey,
+          authority: oldAuthorityKeypair.publicKey,
+          receiver: receiverKeypair.publicKey,
+        })
+        .signers([oldAuthorityKeypair])
+        .rpc();
+      expect.fail("closePuppet should have failed with ConstraintHasOne");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("ConstraintHasOne");
+    }
+
+    const puppetLamports = await provider.connection
+      .getBalance(retiredPuppetKeypair.publicKey);
+
+    await puppetProgram.methods
+      .closePuppet()
+      .accounts({
+        puppet: retiredPuppetKeypair.publicKey,
+        authority: newAuthorityKeypair.publicKey,
+        receiver: receiverKeypair.publicKey,
+      })
+      .signers([newAuthorityKeypair])
+      .rpc();
+
+    expect(await provider.connection
+      .getAccountInfo(retiredPuppetKeypair.publicKey)).to.be.null;
+    expect(await provider.connection
+      .getBalance(receiverKeypair.publicKey)).to.equal(puppetLamports);
+  });
+
+  it('CPI: master releases and retires its puppets', async () => {
+    const releasedPuppetKeypair = Keypair.generate();
+    const retiredPuppetKeypair = Keypair.generate();
+    const newAuthorityKeypair = Keypair.generate();
+    const receiverKeypair = Keypair.generate();
+
+    const puppetAuthorityPdaFor = (puppet: PublicKey) => PublicKey.findProgramAddressSync(
+      [Buffer.from("puppet-authority"), puppet.toBuffer()],
+      masterProgram.programId
+    )[0];
+
+    for (const keypair of [releasedPuppetKeypair, retiredPuppetKeypair]) {
+      await puppetProgram.methods
+        .initialize(provider.wallet.publicKey)
+        .accounts({
+          puppet: keypair.publicKey,
+          user: provider.wallet.publicKey,
+        })
+        .signers([keypair])
+        .rpc();
+
+      await masterProgram.methods
+        .initializePuppetAuthority()
+        .accounts({
+          puppet: keypair.publicKey,
+          puppetProgram: puppetProgram.programId,
+          puppetAuthority: puppetAuthorityPdaFor(keypair.publicKey),
+          owner: provider.wallet.publicKey,
+        })
+        .rpc();
+    }
+
+    // Only the PuppetAuthority owner can release
+    const strangerKeypair = Keypair.generate();
+    try {
+      await masterProgram.methods
+        .releasePuppet(strangerKeypair.publicKey)
+        .accounts({
+          puppet: releasedPuppetKeypair.publicKey,
+          puppetProgram: puppetProgram.programId,
+          authority: puppetAuthorityPdaFor(releasedPuppetKeypair.publicKey),
+          owner: strangerKeypair.publicKey,
+          receiver: strangerKeypair.publicKey,
+        })
+        .signers([strangerKeypair])
+        .rpc();
+      expect.fail("releasePuppet should have failed with Unauthorized");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("Unauthorized");
+    }
+
+    await masterProgram.methods
+      .releasePuppet(newAuthorityKeypair.publicKey)
+      .accounts({
+        puppet: releasedPuppetKeypair.publicKey,
+        puppetProgram: puppetProgram.programId,
+        authority: puppetAuthorityPdaFor(releasedPuppetKeypair.publicKey),
+        owner: provider.wallet.publicKey,
+        receiver: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    expect((await puppetProgram.account.puppet
+      .fetch(releasedPuppetKeypair.publicKey)).authority.toBase58())
+      .to.equal(newAuthorityKeypair.publicKey.toBase58());
+    expect(await provider.connection
+      .getAccountInfo(puppetAuthorityPdaFor(releasedPuppetKeypair.publicKey))).to.be.null;
+
+    // New controller can set data directly now
+    await puppetProgram.methods
+      .setData(new anchor.BN(7))
+      .accounts({
+        puppet: releasedPuppetKeypair.publicKey,
+        authority: newAuthorityKeypair.publicKey,
+      })
+      .signers([newAuthorityKeypair])
+      .rpc();
+
+    await masterProgram.methods
+      .retirePuppet()
+      .accounts({
+        puppet: retiredPuppetKeypair.publicKey,
+        puppetProgram: puppetProgram.programId,
+        authority: puppetAuthorityPdaFor(retiredPuppetKeypair.publicKey),
+        owner: provider.wallet.publicKey,
+        receiver: receiverKeypair.publicKey,
+      })
+      .rpc();
+
+    expect(await provider.connection
+      .getAccountInfo(retiredPuppetKeypair.publicKey)).to.be.null;
+    expect(await provider.connection
+      .getAccountInfo(puppetAuthorityPdaFor(retiredPuppetKeypair.publicKey))).to.be.null;
+    expect(await provider.connection
+      .getBalance(receiverKeypair.publicKey)).to.be.greaterThan(0);
+  });
+
+  it('Records set_data history in a ring buffer', async () => {
+    const historyPuppetKeypair = Keypair.generate();
+    const controllerKeypair = Keypair.generate();
+    const [historyPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("puppet-history"), historyPuppetKeypair.publicKey.toBuffer()],
+      puppetProgram.programId
+    );
+
+    await puppetProgram.methods
+      .initialize(controllerKeypair.publicKey)
+      .accounts({
+        puppet: historyPuppetKeypair.publicKey,
+        user: provider.wallet.publicKey,
+      })
+      .signers([historyPuppetKeypair])
+      .rpc();
+
+    // NOTE Anchor sizes the account before checking the capacity, so
+    // oversized values must still fail with InvalidHistoryCapacity
+    const invalidCapacities = [new anchor.BN(129), new anchor.BN("18446744073709551615")];
+    for (const capacity of invalidCapacities) {
+      try {
+        await puppetProgram.methods
+          .initializePuppetHistory(capacity)
+          .accounts({
+            puppet: historyPuppetKeypair.publicKey,
+            authority: controllerKeypair.publicKey,
+            history: historyPda,
+            payer: provider.wallet.publicKey,
+          })
+          .signers([controllerKeypair])
+          .rpc();
+        expect.fail("initializePuppetHistory should have failed with InvalidHistoryCapacity");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidHistoryCapacity");
+      }
+    }
+    expect(await provider.connection.getAccountInfo(historyPda)).to.be.null;
+
+    await puppetProgram.methods
+      .initializePuppetHistory(new anchor.BN(2))
+      .accounts({
+        puppet: historyPuppetKeypair.publicKey,
+        authority: controllerKeypair.publicKey,
+        history: historyPda,
+        payer: provider.wallet.publicKey,
+      })
+      .signers([controllerKeypair])
+      .rpc();
+    expect((await puppetProgram.account.puppet
+      .fetch(historyPuppetKeypair.publicKey)).hasHistory).to.be.true;
+
+    // Once a history exists, writes can't skip it
+    try {
+      await puppetProgram.methods
+        .setData(new anchor.BN(1))
+        .accounts({
+          puppet: historyPuppetKeypair.publicKey,
+          authority: controllerKeypair.publicKey,
+        })
+        .signers([controllerKeypair])
+        .rpc();
+      expect.fail("setData should have failed with HistoryRequired");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("HistoryRequired");
+    }
+
+    // Write 3 values into a capacity-2 buffer: the first entry is overwritten
+    for (const value of [1, 2, 3]) {
+      await puppetProgram.methods
+        .setData(new anchor.BN(value))
+        .accounts({
+          puppet: historyPuppetKeypair.publicKey,
+          authority: controllerKeypair.publicKey,
+        })
+        .remainingAccounts([{ pubkey: historyPda, isWritable: true, isSigner: false }])
+        .signers([controllerKeypair])
+        .rpc();
+    }
+
+    let history = await puppetProgram.account.puppetHistory.fetch(historyPda);
+    expect(history.puppet.toBase58()).to.equal(historyPuppetKeypair.publicKey.toBase58());
+    expect(history.capacity.toNumber()).to.equal(2);
+    expect(history.len.toNumber()).to.equal(2);
+    expect(history.head.toNumber()).to.equal(1);
+
+    // NOTE Entries follow the header: [discriminator (8)][header (64)][entry (64)]...
+    // entry: slot u64 | unix_timestamp i64 | setter (32) | old_data u64 | new_data u64
+    const decodeEntry = (data: Buffer, index: number) => {
+      const offset = 8 + 64 + index * 64;
+      return {
+        slot: data.readBigUInt64LE(offset),
+        setter: new PublicKey(data.subarray(offset + 16, offset + 48)),
+        oldData: data.readBigUInt64LE(offset + 48),
+        newData: data.readBigUInt64LE(offset + 56),
+      };
+    };
+    let historyInfo = await provider.connection.getAccountInfo(historyPda);
+    const slot0 = decodeEntry(historyInfo.data, 0); // overwritten by 3rd write
+    const slot1 = decodeEntry(historyInfo.data, 1);
+    expect(slot0.oldData).to.equal(BigInt(2));
+    expect(slot0.newData).to.equal(BigInt(3));
+    expect(slot1.oldData).to.equal(BigInt(1));
+    expect(slot1.newData).to.equal(BigInt(2));
+    expect(slot0.setter.toBase58()).to.equal(controllerKeypair.publicKey.toBase58());
+    expect(slot0.slot > BigInt(0)).to.be.true;
+
+    // Resize clears the buffer
+    await puppetProgram.methods
+      .resizePuppetHistory(new anchor.BN(4))
+      .accounts({
+        puppet: historyPuppetKeypair.publicKey,
+        authority: controllerKeypair.publicKey,
+        history: historyPda,
+        payer: provider.wallet.publicKey,
+      })
+      .signers([controllerKeypair])
+      .rpc();
+
+    history = await puppetProgram.account.puppetHistory.fetch(historyPda);
+    expect(history.capacity.toNumber()).to.equal(4);
+    expect(history.len.toNumber()).to.equal(0);
+    historyInfo = await provider.connection.getAccountInfo(historyPda);
+    expect(historyInfo.data.length).to.equal(8 + 64 + 4 * 64);
+    expect(decodeEntry(historyInfo.data, 0).newData).to.equal(BigInt(0));
+
+    // Capacity must be within bounds
+    for (const capacity of [new anchor.BN(0), ...invalidCapacities]) {
+      try {
+        await puppetProgram.methods
+          .resizePuppetHistory(capacity)
+          .accounts({
+            puppet: historyPuppetKeypair.publicKey,
+            authority: controllerKeypair.publicKey,
+            history: historyPda,
+            payer: provider.wallet.publicKey,
+          })
+          .signers([controllerKeypair])
+          .rpc();
+        expect.fail("resizePuppetHistory should have failed with InvalidHistoryCapacity");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidHistoryCapacity");
+      }
+    }
+    expect((await puppetProgram.account.puppetHistory.fetch(historyPda)).capacity.toNumber())
+      .to.equal(4);
+
+    // Closing the puppet closes its history and delegate records too
+    const delegateKeypair = Keypair.generate();
+    const [delegateRecordPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("puppet-delegate"),
+        historyPuppetKeypair.publicKey.toBuffer(),
+        delegateKeypair.publicKey.toBuffer(),
+      ],
+      puppetProgram.programId
+    );
+    await puppetProgram.methods
+      .grantDelegate(delegateKeypair.publicKey, new anchor.BN(0), new anchor.BN(10), null)
+      .accounts({
+        puppet: historyPuppetKeypair.publicKey,
+        authority: controllerKeypair.publicKey,
+        delegateRecord: delegateRecordPda,
+        payer: provider.wallet.publicKey,
+      })
+      .signers([controllerKeypair])
+      .rpc();
+
+    const receiverKeypair = Keypair.generate();
+    const closePuppetAccounts = {
+      puppet: historyPuppetKeypair.publicKey,
+      authority: controllerKeypair.publicKey,
+      receiver: receiverKeypair.publicKey,
+    };
+    try {
+      await puppetProgram.methods
+        .closePuppet()
+        .accounts(closePuppetAccounts)
+        .signers([controllerKeypair])
+        .rpc();
+      expect.fail("closePuppet should have failed with HistoryRequired");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("HistoryRequired");
+    }
+
+    let expectedLamports = 0;
+    for (const pubkey of [historyPuppetKeypair.publicKey, historyPda, delegateRecordPda]) {
+      expectedLamports += await provider.connection.getBalance(pubkey);
+    }
+    await puppetProgram.methods
+      .closePuppet()
+      .accounts(closePuppetAccounts)
+      .remainingAccounts([
+        { pubkey: historyPda, isWritable: true, isSigner: false },
+        { pubkey: delegateRecordPda, isWritable: true, isSigner: false },
+      ])
+      .signers([controllerKeypair])
+      .rpc();
+
+    for (const pubkey of [historyPuppetKeypair.publicKey, historyPda, delegateRecordPda]) {
+      expect(await provider.connection.getAccountInfo(pubkey)).to.be.null;
+    }
+    expect(await provider.connection
+      .getBalance(receiverKeypair.publicKey)).to.equal(expectedLamports);
+  });
+
+  it('Delegates scoped set_data rights', async () => {
+    const delegatedPuppetKeypair = Keypair.generate();
+    const controllerKeypair = Keypair.generate();
+    const delegateKeypair = Keypair.generate();
+    const expiredDelegateKeypair = Keypair.generate();
+
+    const delegateRecordPdaFor = (delegate: PublicKey) => PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("puppet-delegate"),
+        delegatedPuppetKeypair.publicKey.toBuffer(),
+        delegate.toBuffer(),
+      ],
+      puppetProgram.programId
+    )[0];
+    const delegateRecordPda = delegateRecordPdaFor(delegateKeypair.publicKey);
+
+    const setDataAsDelegate = (value: number, delegate: Keypair) => puppetProgram.methods
+      .setData(new anchor.BN(value))
+      .accounts({
+        puppet: delegatedPuppetKeypair.publicKey,
+        authority: delegate.publicKey,
+      })
+      .remainingAccounts([
+        { pubkey: delegateRecordPdaFor(delegate.publicKey), isWritable: false, isSigner: false },
+      ])
+      .signers([delegate])
+      .rpc();
+
+    await puppetProgram.methods
+      .initialize(controllerKeypair.publicKey)
+      .accounts({
+        puppet: delegatedPuppetKeypair.publicKey,
+        user: provider.wallet.publicKey,
+      })
+      .signers([delegatedPuppetKeypair])
+      .rpc();
+
+    await puppetProgram.methods
+      .grantDelegate(delegateKeypair.publicKey, new anchor.BN(10), new anchor.BN(20), null)
+      .accounts({
+        puppet: delegatedPuppetKeypair.publicKey,
+        authority: controllerKeypair.publicKey,
+        delegateRecord: delegateRecordPda,
+        payer: provider.wallet.publicKey,
+      })
+      .signers([controllerKeypair])
+      .rpc();
+
+    // Within range
+    await setDataAsDelegate(15, delegateKeypair);
+    expect((await puppetProgram.account.puppet
+      .fetch(delegatedPuppetKeypair.publicKey)).data.toNumber()).to.equal(15);
+
+    // Out of range
+    try {
+      await setDataAsDelegate(25, delegateKeypair);
+      expect.fail("setData should have failed with DelegateOutOfRange");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("DelegateOutOfRange");
+    }
+
+    // Expired delegation
+    const currentSlot = await provider.connection.getSlot();
+    await puppetProgram.methods
+      .grantDelegate(
+        expiredDelegateKeypair.publicKey,
+        new anchor.BN(0),
+        new anchor.BN(100),
+        new anchor.BN(currentSlot - 1)
+      )
+      .accounts({
+        puppet: delegatedPuppetKeypair.publicKey,
+        authority: controllerKeypair.publicKey,
+        delegateRecord: delegateRecordPdaFor(expiredDelegateKeypair.publicKey),
+        payer: provider.wallet.publicKey,
+      })
+      .signers([controllerKeypair])
+      .rpc();
+
+    try {
+      await setDataAsDelegate(50, expiredDelegateKeypair);
+      expect.fail("setData should have failed with DelegateExpired");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("DelegateExpired");
+    }
+
+    // Revoked delegation
+    await puppetProgram.methods
+      .revokeDelegate()
+      .accounts({
+        puppet: delegatedPuppetKeypair.publicKey,
+        authority: controllerKeypair.publicKey,
+        delegateRecord: delegateRecordPda,
+        receiver: provider.wallet.publicKey,
+      })
+      .signers([controllerKeypair])
+      .rpc();
+
+    try {
+      await setDataAsDelegate(16, delegateKeypair);
+      expect.fail("setData should have failed with Unauthorized");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("Unauthorized");
+    }
+
+    // Authority itself is still unrestricted
+    await puppetProgram.methods
+      .setData(new anchor.BN(1000))
+      .accounts({
+        puppet: delegatedPuppetKeypair.publicKey,
+        authority: controllerKeypair.publicKey,
+      })
+      .signers([controllerKeypair])
+      .rpc();
+    expect((await puppetProgram.account.puppet
+      .fetch(delegatedPuppetKeypair.publicKey)).data.toNumber()).to.equal(1000);
+  });
+
+  it('CPI: pullStringsBatch updates many puppets all-or-nothing', async () => {
+    const fleet = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+    const puppetAuthorityPdaFor = (puppet: PublicKey) => PublicKey.findProgramAddressSync(
+      [Buffer.from("puppet-authority"), puppet.toBuffer()],
+      masterProgram.programId
+    )[0];
+
+    for (const keypair of fleet) {
+      await puppetProgram.methods
+        .initialize(provider.wallet.publicKey)
+        .accounts({
+          puppet: keypair.publicKey,
+          user: provider.wallet.publicKey,
+        })
+        .signers([keypair])
+        .rpc();
+
+      await masterProgram.methods
+        .initializePuppetAuthority()
+        .accounts({
+          puppet: keypair.publicKey,
+          puppetProgram: puppetProgram.programId,
+          puppetAuthority: puppetAuthorityPdaFor(keypair.publicKey),
+          owner: provider.wallet.publicKey,
+        })
+        .rpc();
+    }
+
+    const remainingAccountsFor = (puppets: PublicKey[], authorities: PublicKey[]) =>
+      puppets.flatMap((puppet, i) => [
+        { pubkey: puppet, isWritable: true, isSigner: false },
+        { pubkey: authorities[i], isWritable: false, isSigner: false },
+      ]);
+    const puppets = fleet.map((keypair) => keypair.publicKey);
+    const authorities = puppets.map(puppetAuthorityPdaFor);
+
+    const events = [];
+    const listener = masterProgram.addEventListener(
+      "PuppetStringsPulled",
+      (event) => events.push(event)
+    );
+
+    await masterProgram.methods
+      .pullStringsBatch([new anchor.BN(1), new anchor.BN(2), new anchor.BN(3)])
+      .accounts({ puppetProgram: puppetProgram.programId, owner: provider.wallet.publicKey })
+      .remainingAccounts(remainingAccountsFor(puppets, authorities))
+      .rpc();
+
+    for (const [i, puppet] of puppets.entries()) {
+      expect((await puppetProgram.account.puppet.fetch(puppet)).data.toNumber())
+        .to.equal(i + 1);
+    }
+
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+    await masterProgram.removeEventListener(listener);
+    expect(events.length).to.equal(3);
+    expect(events[2].index).to.equal(2);
+    expect(events[2].puppet.toBase58()).to.equal(puppets[2].toBase58());
+    expect(events[2].previousData.toNumber()).to.equal(0);
+    expect(events[2].newData.toNumber()).to.equal(3);
+
+    // Mismatched authority for the last puppet reverts the whole batch
+    try {
+      await masterProgram.methods
+        .pullStringsBatch([new anchor.BN(10), new anchor.BN(20), new anchor.BN(30)])
+        .accounts({ puppetProgram: puppetProgram.programId, owner: provider.wallet.publicKey })
+        .remainingAccounts(
+          remainingAccountsFor(puppets, [authorities[0], authorities[1], authorities[0]])
+        )
+        .rpc();
+      expect.fail("pullStringsBatch should have failed with PuppetAuthorityMismatch");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("PuppetAuthorityMismatch");
+    }
+    expect((await puppetProgram.account.puppet.fetch(puppets[0])).data.toNumber())
+      .to.equal(1);
+
+    // One (puppet, authority) pair per value
+    try {
+      await masterProgram.methods
+        .pullStringsBatch([new anchor.BN(10), new anchor.BN(20)])
+        .accounts({ puppetProgram: puppetProgram.programId, owner: provider.wallet.publicKey })
+        .remainingAccounts(remainingAccountsFor(puppets, authorities))
+        .rpc();
+      expect.fail("pullStringsBatch should have failed with BatchLengthMismatch");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("BatchLengthMismatch");
+    }
+  });
+
+  it('CPI: master forwards the puppet history on every pull', async () => {
+    const historyPuppetKeypair = Keypair.generate();
+    const [historyPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("puppet-history"), historyPuppetKeypair.publicKey.toBuffer()],
+      puppetProgram.programId
+    );
+    const [puppetAuthorityPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("puppet-authority"), historyPuppetKeypair.publicKey.toBuffer()],
+      masterProgram.programId
+    );
+    const historyAccount = { pubkey: historyPda, isWritable: true, isSigner: false };
+
+    await puppetProgram.methods
+      .initialize(provider.wallet.publicKey)
+      .accounts({
+        puppet: historyPuppetKeypair.publicKey,
+        user: provider.wallet.publicKey,
+      })
+      .signers([historyPuppetKeypair])
+      .rpc();
+
+    // NOTE History is set up while the wallet is still the authority
+    await puppetProgram.methods
+      .initializePuppetHistory(new anchor.BN(4))
+      .accounts({
+        puppet: historyPuppetKeypair.publicKey,
+        authority: provider.wallet.publicKey,
+        history: historyPda,
+        payer: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    await masterProgram.methods
+      .initializePuppetAuthority()
+      .accounts({
+        puppet: historyPuppetKeypair.publicKey,
+        puppetProgram: puppetProgram.programId,
+        puppetAuthority: puppetAuthorityPda,
+        owner: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    const pullStringsAccounts = {
+      puppetProgram: puppetProgram.programId,
+      puppet: historyPuppetKeypair.publicKey,
+      authority: puppetAuthorityPda,
+      owner: provider.wallet.publicKey,
+    };
+
+    try {
+      await masterProgram.methods
+        .pullStrings(new anchor.BN(1))
+        .accounts(pullStringsAccounts)
+        .rpc();
+      expect.fail("pullStrings should have failed with HistoryRequired");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("HistoryRequired");
+    }
+
+    await masterProgram.methods
+      .pullStrings(new anchor.BN(1))
+      .accounts(pullStringsAccounts)
+      .remainingAccounts([historyAccount])
+      .rpc();
+
+    // Batch entries carry the history right after the puppet's authority
+    const batchAccounts = [
+      { pubkey: historyPuppetKeypair.publicKey, isWritable: true, isSigner: false },
+      { pubkey: puppetAuthorityPda, isWritable: false, isSigner: false },
+    ];
+    try {
+      await masterProgram.methods
+        .pullStringsBatch([new anchor.BN(2)])
+        .accounts({ puppetProgram: puppetProgram.programId, owner: provider.wallet.publicKey })
+        .remainingAccounts(batchAccounts)
+        .rpc();
+      expect.fail("pullStringsBatch should have failed with BatchLengthMismatch");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("BatchLengthMismatch");
+    }
+
+    await masterProgram.methods
+      .pullStringsBatch([new anchor.BN(2)])
+      .accounts({ puppetProgram: puppetProgram.programId, owner: provider.wallet.publicKey })
+      .remainingAccounts([...batchAccounts, historyAccount])
+      .rpc();
+
+    const history = await puppetProgram.account.puppetHistory.fetch(historyPda);
+    expect(history.len.toNumber()).to.equal(2);
+    expect((await puppetProgram.account.puppet
+      .fetch(historyPuppetKeypair.publicKey)).data.toNumber()).to.equal(2);
+
+    // Retiring forwards the history too, so it's closed with the puppet
+    const retirePuppetAccounts = {
+      puppet: historyPuppetKeypair.publicKey,
+      puppetProgram: puppetProgram.programId,
+      authority: puppetAuthorityPda,
+      owner: provider.wallet.publicKey,
+      receiver: provider.wallet.publicKey,
+    };
+    try {
+      await masterProgram.methods
+        .retirePuppet()
+        .accounts(retirePuppetAccounts)
+        .rpc();
+      expect.fail("retirePuppet should have failed with HistoryRequired");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("HistoryRequired");
+    }
+
+    await masterProgram.methods
+      .retirePuppet()
+      .accounts(retirePuppetAccounts)
+      .remainingAccounts([historyAccount])
+      .rpc();
+
+    expect(await provider.connection
+      .getAccountInfo(historyPuppetKeypair.publicKey)).to.be.null;
+    expect(await provider.connection.getAccountInfo(historyPda)).to.be.null;
+  });
+
+  it('CPI: routeCpi forwards allowlisted instructions signed by the router PDA', async () => {
+    const [programAllowlistPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("program-allowlist")],
+      masterProgram.programId
+    );
+    const [routerSignerPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("router-signer")],
+      masterProgram.programId
+    );
+    // NOTE 'anchor test' deploys master as upgradeable, with the wallet
+    // as its upgrade authority
+    const [masterProgramDataPda] = PublicKey.findProgramAddressSync(
+      [masterProgram.programId.toBuffer()],
+      new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+    );
+    const routedPuppetKeypair = Keypair.generate();
+
+    // Only the upgrade authority can create the allowlist
+    const strangerKeypair = Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(strangerKeypair.publicKey, anchor.web3.LAMPORTS_PER_SOL)
+    );
+    try {
+      await masterProgram.methods
+        .initializeProgramAllowlist()
+        .accounts({
+          programAllowlist: programAllowlistPda,
+          routerSigner: routerSignerPda,
+          masterProgram: masterProgram.programId,
+          masterProgramData: masterProgramDataPda,
+          admin: strangerKeypair.publicKey,
+        })
+        .signers([strangerKeypair])
+        .rpc();
+      expect.fail("initializeProgramAllowlist should have failed with Unauthorized");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("Unauthorized");
+    }
+
+    await masterProgram.methods
+      .initializeProgramAllowlist()
+      .accounts({
+        programAllowlist: programAllowlistPda,
+        routerSigner: routerSignerPda,
+        masterProgram: masterProgram.programId,
+        masterProgramData: masterProgramDataPda,
+        admin: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    await masterProgram.methods
+      .addAllowedProgram(puppetProgram.programId)
+      .accounts({
+        programAllowlist: programAllowlistPda,
+        admin: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    expect((await masterProgram.account.programAllowlist.fetch(programAllowlistPda))
+      .programs.map((program) => program.toBase58()))
+      .to.deep.equal([puppetProgram.programId.toBase58()]);
+
+    // Puppet controlled by the router signer PDA
+    await puppetProgram.methods
+      .initialize(routerSignerPda)
+      .accounts({
+        puppet: routedPuppetKeypair.publicKey,
+        user: provider.wallet.publicKey,
+      })
+      .signers([routedPuppetKeypair])
+      .rpc();
+
+    // NOTE Build the target ix client-side, then hand its data + accounts to master
+    const setDataIx = await puppetProgram.methods
+      .setData(new anchor.BN(77))
+      .accounts({
+        puppet: routedPuppetKeypair.publicKey,
+        authority: routerSignerPda,
+      })
+      .instruction();
+    const routedAccounts = setDataIx.keys.map((key) => ({ ...key, isSigner: false }));
+
+    const routeCpi = () => masterProgram.methods
+      .routeCpi(setDataIx.data)
+      .accounts({
+        programAllowlist: programAllowlistPda,
+        admin: provider.wallet.publicKey,
+        targetProgram: puppetProgram.programId,
+        routerSigner: routerSignerPda,
+      })
+      .remainingAccounts(routedAccounts)
+      .rpc();
+
+    await routeCpi();
+    expect((await puppetProgram.account.puppet
+      .fetch(routedPuppetKeypair.publicKey)).data.toNumber()).to.equal(77);
+
+    // Removed programs can no longer be routed to
+    await masterProgram.methods
+      .removeAllowedProgram(puppetProgram.programId)
+      .accounts({
+        programAllowlist: programAllowlistPda,
+        admin: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    try {
+      await routeCpi();
+      expect.fail("routeCpi should have failed with ProgramNotAllowlisted");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("ProgramNotAllowlisted");
+    }
+
+    // Only the admin can manage the allowlist
+    const strangerKeypair = Keypair.generate();
+    try {
+      await masterProgram.methods
+        .addAllowedProgram(puppetProgram.programId)
+        .accounts({
+          programAllowlist: programAllowlistPda,
+          admin: strangerKeypair.publicKey,
+        })
+        .signers([strangerKeypair])
+        .rpc();
+      expect.fail("addAllowedProgram should have failed with Unauthorized");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("Unauthorized");
+    }
+  });
+
   // // Scenario A: authority is a Keypair
   // const authorityKeypair = Keypair.generate();
 
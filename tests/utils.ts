@@ -13,6 +13,24 @@ export async function createKeypairFromFile(
   return anchor.web3.Keypair.fromSecretKey(secretKey);
 }
 
+// Metaplex Token Metadata Program (loaded via Anchor.toml [[test.genesis]])
+export const TOKEN_METADATA_PROGRAM_ID = new anchor.web3.PublicKey(
+  "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
+);
+
+// Derive the Metaplex metadata PDA for a mint
+export function findMetadataPda(mint: anchor.web3.PublicKey): anchor.web3.PublicKey {
+  const [metadataPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("metadata"),
+      TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+      mint.toBuffer(),
+    ],
+    TOKEN_METADATA_PROGRAM_ID
+  );
+  return metadataPda;
+}
+
 // // Alternate syntax:
 // export function createKeypairFromFile(filepath: string): Keypair {
 //   return Keypair.fromSecretKey(